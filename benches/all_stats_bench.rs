@@ -1,3 +1,5 @@
+#![allow(clippy::needless_borrow, unused_mut)]
+
 use criterion::{black_box, criterion_group, Criterion};
 use incr_stats::{batch, error::Result, incr, vec};
 use rand::Rng;
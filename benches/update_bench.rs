@@ -3,6 +3,9 @@ use incr_stats::incr;
 use rand::Rng;
 
 const MAX_DATA_SIZE: u32 = 1_000_000;
+// Sizes that fit in the cache, and that must be read from memory.
+const CACHED_DATA_SIZE: u32 = 10_000;
+const UNCACHED_DATA_SIZE: u32 = 10_000_000;
 
 macro_rules! update {
     ($c:expr, $count:expr) => {
@@ -22,6 +25,30 @@ macro_rules! update {
     };
 }
 
+macro_rules! array_update {
+    ($c:expr, $count:expr) => {
+        let mut rng = rand::thread_rng();
+        let mut a = vec![];
+        for _ in 0..$count {
+            a.push(rng.gen())
+        }
+        $c.bench_function(&format!("array_update_{}_incr", $count), |b| {
+            b.iter(|| {
+                let mut d = incr::Stats::new();
+                black_box(d.array_update(black_box(&a)).unwrap());
+                black_box(d)
+            })
+        });
+        $c.bench_function(&format!("update_many_{}_incr", $count), |b| {
+            b.iter(|| {
+                let mut d = incr::Stats::new();
+                black_box(d.update_many(black_box(&a)).unwrap());
+                black_box(d)
+            })
+        });
+    };
+}
+
 macro_rules! final_calc {
     ($c:expr, $count:expr) => {
         let mut rng = rand::thread_rng();
@@ -45,6 +72,18 @@ pub fn update_1mm(c: &mut Criterion) {
     update!(c, MAX_DATA_SIZE);
 }
 
+pub fn array_update_1mm(c: &mut Criterion) {
+    array_update!(c, MAX_DATA_SIZE);
+}
+
+pub fn array_update_10k(c: &mut Criterion) {
+    array_update!(c, CACHED_DATA_SIZE);
+}
+
+pub fn array_update_10mm(c: &mut Criterion) {
+    array_update!(c, UNCACHED_DATA_SIZE);
+}
+
 pub fn final_calc_10(c: &mut Criterion) {
     final_calc!(c, 10);
 }
@@ -57,6 +96,9 @@ criterion_group!(
     benches,
     update_10,
     update_1mm,
+    array_update_1mm,
+    array_update_10k,
+    array_update_10mm,
     final_calc_10,
     final_calc_1mm,
 );
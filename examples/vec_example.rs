@@ -5,7 +5,7 @@ fn main() {
 
     // Initialize the structure with the data. It will be checked for invalid data such as NaNs or
     // Infs.
-    let mut d = match Stats::new(&a) {
+    let d = match Stats::new(&a) {
         Ok(d) => d,
         Err(e) => {
             println!("Data contains invalid values: {}", e);
@@ -1,4 +1,23 @@
+#[cfg(feature = "alloc")]
+use crate::dist;
 use crate::error::{Result, StatsError};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::float::Float;
+#[cfg(feature = "alloc")]
+use crate::hist::{self, BinRule, Bins};
+#[cfg(feature = "alloc")]
+use crate::hypothesis::{self, TestResult};
+#[cfg(feature = "alloc")]
+use crate::incr::Stats;
+#[cfg(feature = "rayon")]
+use crate::par;
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 //
 // Batch functions
@@ -13,6 +32,14 @@ use crate::error::{Result, StatsError};
 
 // Check that the data contains no NaNs, Infs, or -Infs.
 pub fn validate(data: &[f64]) -> Result<()> {
+    #[cfg(feature = "rayon")]
+    if let Some(valid) = par::is_valid(data) {
+        return if valid {
+            Ok(())
+        } else {
+            Err(StatsError::InvalidData)
+        };
+    }
     for v in data {
         if f64::is_nan(*v) || f64::is_infinite(*v) {
             return Err(StatsError::InvalidData);
@@ -29,6 +56,10 @@ pub fn min(data: &[f64]) -> Result<f64> {
     if data.is_empty() {
         return Err(StatsError::NotEnoughData);
     }
+    #[cfg(feature = "rayon")]
+    if let Some(min) = par::min(data) {
+        return Ok(min);
+    }
     Ok(data.iter().fold(f64::INFINITY, |a, &b| a.min(b)))
 }
 
@@ -36,13 +67,84 @@ pub fn max(data: &[f64]) -> Result<f64> {
     if data.is_empty() {
         return Err(StatsError::NotEnoughData);
     }
+    #[cfg(feature = "rayon")]
+    if let Some(max) = par::max(data) {
+        return Ok(max);
+    }
     Ok(data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b)))
 }
 
+// The difference between the largest and smallest values.
+// R: diff(range(a))
+// Octave: range(a)
+pub fn range(data: &[f64]) -> Result<f64> {
+    Ok(max(data)? - min(data)?)
+}
+
+// R: median(a)
+// Octave: median(a)
+#[cfg(feature = "alloc")]
+pub fn median(data: &[f64]) -> Result<f64> {
+    if data.is_empty() {
+        return Err(StatsError::NotEnoughData);
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    Ok(sorted_quantile(&sorted, 0.5))
+}
+
+// The interquartile range, the difference between the 75th and 25th percentiles. The percentiles
+// interpolate linearly between the sorted values.
+// R: IQR(a)
+// Octave: quantile(a, 0.75, 1, 7) - quantile(a, 0.25, 1, 7)
+#[cfg(feature = "alloc")]
+pub fn interquartile_range(data: &[f64]) -> Result<f64> {
+    if data.is_empty() {
+        return Err(StatsError::NotEnoughData);
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    Ok(sorted_quantile(&sorted, 0.75) - sorted_quantile(&sorted, 0.25))
+}
+
+// The p quantile of sorted, non-empty data, interpolating linearly between the values. This is
+// R's default quantile type 7.
+#[cfg(feature = "alloc")]
+pub(crate) fn sorted_quantile(sorted: &[f64], p: f64) -> f64 {
+    let position = p * (sorted.len() - 1) as f64;
+    let i = position as usize;
+    let fraction = position - i as f64;
+    if fraction == 0.0 {
+        sorted[i]
+    } else {
+        sorted[i] + fraction * (sorted[i + 1] - sorted[i])
+    }
+}
+
+// How rank() ranks tied values.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Ties {
+    // The average of the ranks that the tied values span.
+    Average,
+    // The lowest of the ranks that the tied values span.
+    Min,
+    // The highest of the ranks that the tied values span.
+    Max,
+    // The lowest rank, with the next larger value ranked one higher, so the ranks have no gaps.
+    Dense,
+    // Distinct ranks in the order that the tied values appear.
+    Ordinal,
+}
+
 pub fn sum(data: &[f64]) -> Result<f64> {
     if data.is_empty() {
         return Err(StatsError::NotEnoughData);
     }
+    #[cfg(feature = "rayon")]
+    if let Some(sum) = par::sum(data) {
+        return Ok(sum);
+    }
     Ok(data.iter().fold(0.0, |sum, v| sum + v))
 }
 
@@ -70,6 +172,10 @@ pub fn population_variance(data: &[f64]) -> Result<f64> {
     if data.len() <= 1 {
         return Err(StatsError::NotEnoughData);
     }
+    #[cfg(feature = "rayon")]
+    if let Some(stats) = par::stats(data) {
+        return stats.population_variance();
+    }
     Ok(sum_squared_deltas(data)? / (data.len() as f64))
 }
 
@@ -80,6 +186,10 @@ pub fn sample_variance(data: &[f64]) -> Result<f64> {
     if data.len() <= 1 {
         return Err(StatsError::NotEnoughData);
     }
+    #[cfg(feature = "rayon")]
+    if let Some(stats) = par::stats(data) {
+        return stats.sample_variance();
+    }
     Ok(sum_squared_deltas(data)? / ((data.len() - 1) as f64))
 }
 
@@ -105,6 +215,10 @@ pub fn population_skewness(data: &[f64]) -> Result<f64> {
     if data.len() <= 1 {
         return Err(StatsError::NotEnoughData);
     }
+    #[cfg(feature = "rayon")]
+    if let Some(stats) = par::stats(data) {
+        return stats.population_skewness();
+    }
     let mean = mean(data)?;
     let sum3 = data.iter().fold(0.0, |sum, v| {
         let delta = v - mean;
@@ -146,6 +260,10 @@ pub fn population_kurtosis(data: &[f64]) -> Result<f64> {
     if data.len() <= 1 {
         return Err(StatsError::NotEnoughData);
     }
+    #[cfg(feature = "rayon")]
+    if let Some(stats) = par::stats(data) {
+        return stats.population_kurtosis();
+    }
     let mean = mean(data)?;
     let n = data.len() as f64;
 
@@ -172,7 +290,554 @@ pub fn sample_kurtosis(data: &[f64]) -> Result<f64> {
     Ok((n - 1.0) / ((n - 2.0) * (n - 3.0)) * ((n + 1.0) * population_kurtosis(data)? + 6.0))
 }
 
+// Evaluate the polynomial c[0] + c[1] x + c[2] x^2 + ...
+#[cfg(feature = "alloc")]
+fn polynomial(c: &[f64], x: f64) -> f64 {
+    c.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+// Shapiro-Wilk test of normality, using Royston's algorithm AS R94 for the coefficients and the
+// p-value. Small p-values indicate that the data is unlikely to be normally distributed. Requires
+// 3 to 5000 values, and returns InvalidParameter for more.
+// R: shapiro.test(a)
+#[cfg(feature = "alloc")]
+pub fn shapiro_wilk(data: &[f64]) -> Result<TestResult> {
+    const SMALL: f64 = 1e-19;
+    const C1: [f64; 6] = [0.0, 0.221157, -0.147981, -2.07119, 4.434685, -2.706056];
+    const C2: [f64; 6] = [0.0, 0.042981, -0.293762, -1.752461, 5.682633, -3.582633];
+    const C3: [f64; 4] = [0.544, -0.39978, 0.025054, -6.714e-4];
+    const C4: [f64; 4] = [1.3822, -0.77857, 0.062767, -0.0020322];
+    const C5: [f64; 4] = [-1.5861, -0.31082, -0.083751, 0.0038915];
+    const C6: [f64; 3] = [-0.4803, -0.082676, 0.0030302];
+    const G: [f64; 2] = [-2.273, 0.459];
+
+    validate(data)?;
+    let n = data.len();
+    if n < 3 {
+        return Err(StatsError::NotEnoughData);
+    }
+    // Royston's approximations are only valid up to 5000 values.
+    if n > 5000 {
+        return Err(StatsError::InvalidParameter);
+    }
+    let mut x = data.to_vec();
+    x.sort_by(f64::total_cmp);
+    let range = x[n - 1] - x[0];
+    if range < SMALL {
+        return Err(StatsError::Undefined);
+    }
+
+    // The coefficients for the lower half of the sorted data. The upper half is antisymmetric.
+    let nf = n as f64;
+    let half = n / 2;
+    let mut a = vec![0.0; half];
+    if n == 3 {
+        a[0] = f64::sqrt(0.5);
+    } else {
+        let m: Vec<f64> = (0..half)
+            .map(|i| dist::normal_quantile((i as f64 + 1.0 - 0.375) / (nf + 0.25)))
+            .collect::<Result<_>>()?;
+        let summ2 = 2.0 * m.iter().map(|v| v * v).sum::<f64>();
+        let ssumm2 = f64::sqrt(summ2);
+        let rsn = 1.0 / f64::sqrt(nf);
+        let a1 = polynomial(&C1, rsn) - m[0] / ssumm2;
+        let (first, fac) = if n > 5 {
+            let a2 = -m[1] / ssumm2 + polynomial(&C2, rsn);
+            a[1] = a2;
+            let fac = f64::sqrt(
+                (summ2 - 2.0 * m[0] * m[0] - 2.0 * m[1] * m[1])
+                    / (1.0 - 2.0 * a1 * a1 - 2.0 * a2 * a2),
+            );
+            (2, fac)
+        } else {
+            let fac = f64::sqrt((summ2 - 2.0 * m[0] * m[0]) / (1.0 - 2.0 * a1 * a1));
+            (1, fac)
+        };
+        a[0] = a1;
+        for i in first..half {
+            a[i] = -m[i] / fac;
+        }
+    }
+
+    // W is the squared correlation between the sorted data and the coefficients. Calculate 1 - W
+    // directly to keep its precision when W is close to 1.
+    let coefficient = |i: usize| {
+        if i < half {
+            -a[i]
+        } else if n - 1 - i < half {
+            a[n - 1 - i]
+        } else {
+            0.0
+        }
+    };
+    let sa = (0..n).map(coefficient).sum::<f64>() / nf;
+    let sx = x.iter().map(|v| v / range).sum::<f64>() / nf;
+    let (mut ssa, mut ssx, mut sax) = (0.0, 0.0, 0.0);
+    for (i, v) in x.iter().enumerate() {
+        let asa = coefficient(i) - sa;
+        let xsx = v / range - sx;
+        ssa += asa * asa;
+        ssx += xsx * xsx;
+        sax += asa * xsx;
+    }
+    let ssassx = f64::sqrt(ssa * ssx);
+    let w1 = (ssassx - sax) * (ssassx + sax) / (ssa * ssx);
+    let w = 1.0 - w1;
+
+    // The p-value is exact for 3 values and otherwise uses Royston's normalizing transformation.
+    if n == 3 {
+        let p_value =
+            6.0 / core::f64::consts::PI * (f64::asin(f64::sqrt(w)) - core::f64::consts::PI / 3.0);
+        return Ok(TestResult {
+            statistic: w,
+            p_value: f64::max(p_value, 0.0),
+        });
+    }
+    let mut y = f64::ln(w1);
+    let (m, s) = if n <= 11 {
+        let gamma = polynomial(&G, nf);
+        if y >= gamma {
+            return Ok(TestResult {
+                statistic: w,
+                p_value: 0.0,
+            });
+        }
+        y = -f64::ln(gamma - y);
+        (polynomial(&C3, nf), f64::exp(polynomial(&C4, nf)))
+    } else {
+        let ln_n = f64::ln(nf);
+        (polynomial(&C5, ln_n), f64::exp(polynomial(&C6, ln_n)))
+    };
+    let z = (y - m) / s;
+    let p_value = if z > 0.0 {
+        dist::normal_tail(z)
+    } else {
+        1.0 - dist::normal_tail(z)
+    };
+    Ok(TestResult {
+        statistic: w,
+        p_value,
+    })
+}
+
+// Anderson-Darling test of normality, with the mean and variance estimated from the data. The
+// p-value is from the approximation of D'Agostino and Stephens (1986), which is only accurate to a
+// few digits. Requires at least 8 values.
+// R: library(nortest); ad.test(a)
+#[cfg(feature = "alloc")]
+pub fn anderson_darling(data: &[f64]) -> Result<TestResult> {
+    validate(data)?;
+    if data.len() < 8 {
+        return Err(StatsError::NotEnoughData);
+    }
+    let mean = mean(data)?;
+    let sd = sample_standard_deviation(data)?;
+    if sd == 0.0 {
+        return Err(StatsError::Undefined);
+    }
+    let mut z: Vec<f64> = data.iter().map(|v| (v - mean) / sd).collect();
+    z.sort_by(f64::total_cmp);
+    let n = data.len() as f64;
+    // ln(Phi(z)), computed from whichever tail keeps its precision.
+    let ln_cdf = |z: f64| {
+        if z < 0.0 {
+            f64::ln(dist::normal_tail(z))
+        } else {
+            f64::ln_1p(-dist::normal_tail(z))
+        }
+    };
+    let sum = z
+        .iter()
+        .zip(z.iter().rev())
+        .enumerate()
+        .fold(0.0, |sum, (i, (lo, hi))| {
+            sum + (2.0 * i as f64 + 1.0) * (ln_cdf(*lo) + ln_cdf(-hi))
+        });
+    let statistic = -n - sum / n;
+
+    let aa = (1.0 + 0.75 / n + 2.25 / (n * n)) * statistic;
+    let p_value = if aa < 0.2 {
+        1.0 - f64::exp(-13.436 + 101.14 * aa - 223.73 * aa * aa)
+    } else if aa < 0.34 {
+        1.0 - f64::exp(-8.318 + 42.796 * aa - 59.938 * aa * aa)
+    } else if aa < 0.6 {
+        f64::exp(0.9177 - 4.279 * aa - 1.38 * aa * aa)
+    } else if aa < 10.0 {
+        f64::exp(1.2937 - 5.709 * aa + 0.0186 * aa * aa)
+    } else {
+        3.7e-24
+    };
+    Ok(TestResult { statistic, p_value })
+}
+
+// The rank of each value, from 1 for the smallest, in the order of the data.
+// R: rank(a, ties.method = "average"), or "min", "max", or "first" for ordinal ranks
+// Octave: tiedrank(a)
+#[cfg(feature = "alloc")]
+pub fn rank(data: &[f64], ties: Ties) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..data.len()).collect();
+    // The sort is stable, so tied values stay in their original order for ordinal ranks.
+    order.sort_by(|&i, &j| f64::total_cmp(&data[i], &data[j]));
+    let mut ranks = vec![0.0; data.len()];
+    let mut dense = 0.0;
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && data[order[end]] == data[order[start]] {
+            end += 1;
+        }
+        dense += 1.0;
+        for (k, &i) in order[start..end].iter().enumerate() {
+            ranks[i] = match ties {
+                Ties::Average => 0.5 * (start + end + 1) as f64,
+                Ties::Min => (start + 1) as f64,
+                Ties::Max => end as f64,
+                Ties::Dense => dense,
+                Ties::Ordinal => (start + k + 1) as f64,
+            };
+        }
+        start = end;
+    }
+    ranks
+}
+
+// Autocorrelations at lags 0 to max_lag, the correlations of the series with itself shifted by each
+// lag. The autocovariances are all divided by the length of the series, as is usual for time series.
+// R: acf(a, lag.max = max_lag, plot = FALSE)$acf
+// Octave: autocorr(a, max_lag)
+#[cfg(feature = "alloc")]
+pub fn acf(data: &[f64], max_lag: usize) -> Result<Vec<f64>> {
+    validate(data)?;
+    if max_lag >= data.len() {
+        return Err(StatsError::NotEnoughData);
+    }
+    let mean = mean(data)?;
+    let autocovariances: Vec<f64> = (0..=max_lag)
+        .map(|lag| {
+            data.iter()
+                .zip(&data[lag..])
+                .fold(0.0, |sum, (a, b)| sum + (a - mean) * (b - mean))
+        })
+        .collect();
+    if autocovariances[0] == 0.0 {
+        return Err(StatsError::Undefined);
+    }
+    Ok(autocovariances
+        .iter()
+        .map(|c| c / autocovariances[0])
+        .collect())
+}
+
+// Partial autocorrelations at lags 1 to max_lag, the correlation at each lag after removing the
+// effect of the shorter lags.
+// R: pacf(a, lag.max = max_lag, plot = FALSE)$acf
+// Octave: parcorr(a, max_lag)
+#[cfg(feature = "alloc")]
+pub fn pacf(data: &[f64], max_lag: usize) -> Result<Vec<f64>> {
+    Ok(partial_autocorrelations(&acf(data, max_lag)?))
+}
+
+// The partial autocorrelations from the autocorrelations at lags 0 to k, by the Durbin-Levinson
+// recursion, which fits autoregressive models of increasing order.
+#[cfg(feature = "alloc")]
+pub(crate) fn partial_autocorrelations(acf: &[f64]) -> Vec<f64> {
+    let mut partial = Vec::with_capacity(acf.len().saturating_sub(1));
+    let mut phi: Vec<f64> = Vec::with_capacity(acf.len());
+    for k in 1..acf.len() {
+        let numerator = acf[k] - (0..k - 1).map(|j| phi[j] * acf[k - 1 - j]).sum::<f64>();
+        let denominator = 1.0 - (0..k - 1).map(|j| phi[j] * acf[j + 1]).sum::<f64>();
+        let phi_kk = numerator / denominator;
+        phi = (0..k - 1)
+            .map(|j| phi[j] - phi_kk * phi[k - 2 - j])
+            .chain(core::iter::once(phi_kk))
+            .collect();
+        partial.push(phi_kk);
+    }
+    partial
+}
+
+// Ljung-Box test that the series has no serial correlation up to the given number of lags. A small
+// p-value means that the values are not independent.
+// R: Box.test(a, lag = lags, type = "Ljung-Box")
+// Octave: lbqtest(a, "Lags", lags)
+#[cfg(feature = "alloc")]
+pub fn ljung_box(data: &[f64], lags: usize) -> Result<TestResult> {
+    if lags == 0 {
+        return Err(StatsError::InvalidParameter);
+    }
+    let acf = acf(data, lags)?;
+    Ok(hypothesis::ljung_box(data.len() as f64, &acf[1..]))
+}
+
+// Histogram of the data, returning the count in each bin and the bin edges. The bins may be given
+// as a count or as edges, or chosen by a rule. Each bin includes its lower edge, and the last bin
+// also includes its upper edge.
+// R: hist(a, breaks = edges, right = FALSE, plot = FALSE)$counts
+#[cfg(feature = "alloc")]
+pub fn histogram(data: &[f64], bins: Bins) -> Result<(Vec<u32>, Vec<f64>)> {
+    validate(data)?;
+    let edges = match bins {
+        Bins::Edges(edges) => {
+            hist::validate_edges(edges)?;
+            edges.to_vec()
+        }
+        Bins::Count(count) => {
+            if count == 0 {
+                return Err(StatsError::InvalidParameter);
+            }
+            hist::equal_count_edges(min(data)?, max(data)?, count)?
+        }
+        Bins::Rule(rule) => {
+            let (min, max) = (min(data)?, max(data)?);
+            let range = max - min;
+            let n = data.len() as f64;
+            // Constant data has a single bin, whatever the rule.
+            let width = if range == 0.0 {
+                0.0
+            } else {
+                match rule {
+                    BinRule::Auto => hist::auto_width(n, range, interquartile_range(data)?),
+                    BinRule::Sturges => hist::sturges_width(n, range),
+                    BinRule::Scott => hist::scott_width(n, population_standard_deviation(data)?),
+                    BinRule::FreedmanDiaconis => {
+                        hist::freedman_diaconis_width(n, interquartile_range(data)?)
+                    }
+                    BinRule::Sqrt => hist::sqrt_width(n, range),
+                    BinRule::Doane => hist::doane_width(n, range, population_skewness(data)?),
+                }
+            };
+            hist::equal_width_edges(min, max, width)?
+        }
+    };
+    Ok((hist::bin_counts(data, &edges), edges))
+}
+
+// The statistics of the data up to and including one of its values, from cumulative_stats(). The
+// statistics that are undefined for so few values, or without any variance, are NaN.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CumulativeStats {
+    pub count: u32,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub population_variance: f64,
+    pub sample_variance: f64,
+    pub population_skewness: f64,
+    pub sample_skewness: f64,
+    pub population_kurtosis: f64,
+    pub sample_kurtosis: f64,
+}
+
+// The statistics after each value, like pandas' expanding(), from a single pass of incr Stats.
+// R: cumsum(a) / seq_along(a), and similarly for the other statistics
+#[cfg(feature = "alloc")]
+pub fn cumulative_stats(data: &[f64]) -> Result<Vec<CumulativeStats>> {
+    validate(data)?;
+    let mut stats = Stats::new();
+    let mut cumulative = Vec::with_capacity(data.len());
+    for x in data {
+        stats.update(*x)?;
+        cumulative.push(CumulativeStats {
+            count: stats.count(),
+            sum: stats.sum().unwrap_or(f64::NAN),
+            min: stats.min().unwrap_or(f64::NAN),
+            max: stats.max().unwrap_or(f64::NAN),
+            mean: stats.mean().unwrap_or(f64::NAN),
+            population_variance: stats.population_variance().unwrap_or(f64::NAN),
+            sample_variance: stats.sample_variance().unwrap_or(f64::NAN),
+            population_skewness: stats.population_skewness().unwrap_or(f64::NAN),
+            sample_skewness: stats.sample_skewness().unwrap_or(f64::NAN),
+            population_kurtosis: stats.population_kurtosis().unwrap_or(f64::NAN),
+            sample_kurtosis: stats.sample_kurtosis().unwrap_or(f64::NAN),
+        });
+    }
+    Ok(cumulative)
+}
+
+// Where each rolling window sits relative to the value it's reported at.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Align {
+    // The window ends at the value, so it covers the value and the ones before it.
+    Right,
+    // The window is centered on the value. An even window extends one further before the value.
+    Center,
+    // The window starts at the value.
+    Left,
+}
+
+// Rolling statistics
+//
+// The rolling functions return one result for each value, the statistic of the window of the given
+// size aligned with that value, or NaN where the window runs off the end of the data or the
+// statistic is undefined. They run in O(n) time: the moments are updated as each value enters the
+// window and leaves it, rather than recalculated for every window, and the min and max are kept in
+// monotonic queues.
+//
+// R: zoo::rollapply(a, window, f, fill = NA, align = "right")
+
+#[cfg(feature = "alloc")]
+pub fn rolling_sum(data: &[f64], window: usize, align: Align) -> Result<Vec<f64>> {
+    rolling_moments(data, window, align, Stats::sum)
+}
+
+#[cfg(feature = "alloc")]
+pub fn rolling_mean(data: &[f64], window: usize, align: Align) -> Result<Vec<f64>> {
+    rolling_moments(data, window, align, Stats::mean)
+}
+
+#[cfg(feature = "alloc")]
+pub fn rolling_population_variance(data: &[f64], window: usize, align: Align) -> Result<Vec<f64>> {
+    rolling_moments(data, window, align, Stats::population_variance)
+}
+
+#[cfg(feature = "alloc")]
+pub fn rolling_sample_variance(data: &[f64], window: usize, align: Align) -> Result<Vec<f64>> {
+    rolling_moments(data, window, align, Stats::sample_variance)
+}
+
+#[cfg(feature = "alloc")]
+pub fn rolling_population_standard_deviation(
+    data: &[f64],
+    window: usize,
+    align: Align,
+) -> Result<Vec<f64>> {
+    rolling_moments(data, window, align, Stats::population_standard_deviation)
+}
+
+#[cfg(feature = "alloc")]
+pub fn rolling_sample_standard_deviation(
+    data: &[f64],
+    window: usize,
+    align: Align,
+) -> Result<Vec<f64>> {
+    rolling_moments(data, window, align, Stats::sample_standard_deviation)
+}
+
+#[cfg(feature = "alloc")]
+pub fn rolling_population_skewness(data: &[f64], window: usize, align: Align) -> Result<Vec<f64>> {
+    rolling_moments(data, window, align, Stats::population_skewness)
+}
+
+#[cfg(feature = "alloc")]
+pub fn rolling_sample_skewness(data: &[f64], window: usize, align: Align) -> Result<Vec<f64>> {
+    rolling_moments(data, window, align, Stats::sample_skewness)
+}
+
+#[cfg(feature = "alloc")]
+pub fn rolling_population_kurtosis(data: &[f64], window: usize, align: Align) -> Result<Vec<f64>> {
+    rolling_moments(data, window, align, Stats::population_kurtosis)
+}
+
+#[cfg(feature = "alloc")]
+pub fn rolling_sample_kurtosis(data: &[f64], window: usize, align: Align) -> Result<Vec<f64>> {
+    rolling_moments(data, window, align, Stats::sample_kurtosis)
+}
+
+#[cfg(feature = "alloc")]
+pub fn rolling_min(data: &[f64], window: usize, align: Align) -> Result<Vec<f64>> {
+    rolling_extreme(data, window, align, |a, b| a <= b)
+}
+
+#[cfg(feature = "alloc")]
+pub fn rolling_max(data: &[f64], window: usize, align: Align) -> Result<Vec<f64>> {
+    rolling_extreme(data, window, align, |a, b| a >= b)
+}
+
+// Calculate the statistic of each window from incr Stats that values are added to and removed from.
+// Removal leaves rounding errors, so the stats are rebuilt from the window's values each time the
+// window has moved its whole width, which bounds the errors without changing the O(n) time. They
+// are also rebuilt whenever the window fills with equal values, which otherwise could have a tiny
+// nonzero variance.
+#[cfg(feature = "alloc")]
+fn rolling_moments(
+    data: &[f64],
+    window: usize,
+    align: Align,
+    statistic: fn(&Stats) -> Result<f64>,
+) -> Result<Vec<f64>> {
+    validate_window(data, window)?;
+    let mut stats = Stats::new();
+    let mut run = 0;
+    let mut ends = Vec::with_capacity(data.len());
+    for (i, &x) in data.iter().enumerate() {
+        run = if i > 0 && data[i - 1] == x {
+            run + 1
+        } else {
+            1
+        };
+        if run == window || (i >= window && (i + 1) % window == 0) {
+            stats = Stats::new();
+            stats.array_update(&data[i + 1 - window..=i])?;
+        } else {
+            stats.update(x)?;
+            if i >= window {
+                stats.remove(data[i - window]);
+            }
+        }
+        ends.push(if i + 1 >= window {
+            statistic(&stats).unwrap_or(f64::NAN)
+        } else {
+            f64::NAN
+        });
+    }
+    Ok(aligned(ends, window, align))
+}
+
+// The min or max of each window, from a queue of the indexes of the values that could still be the
+// extreme of a later window. `keeps(a, b)` is true if a is at least as extreme as b.
+#[cfg(feature = "alloc")]
+fn rolling_extreme(
+    data: &[f64],
+    window: usize,
+    align: Align,
+    keeps: fn(f64, f64) -> bool,
+) -> Result<Vec<f64>> {
+    validate_window(data, window)?;
+    let mut queue: VecDeque<usize> = VecDeque::with_capacity(window);
+    let mut ends = Vec::with_capacity(data.len());
+    for (i, &x) in data.iter().enumerate() {
+        while queue.back().is_some_and(|&j| keeps(x, data[j])) {
+            queue.pop_back();
+        }
+        queue.push_back(i);
+        if queue[0] + window <= i {
+            queue.pop_front();
+        }
+        ends.push(if i + 1 >= window {
+            data[queue[0]]
+        } else {
+            f64::NAN
+        });
+    }
+    Ok(aligned(ends, window, align))
+}
+
+#[cfg(feature = "alloc")]
+fn validate_window(data: &[f64], window: usize) -> Result<()> {
+    if window == 0 {
+        return Err(StatsError::InvalidParameter);
+    }
+    validate(data)
+}
+
+// Move the results for the windows ending at each value to the values they are aligned with.
+#[cfg(feature = "alloc")]
+fn aligned(ends: Vec<f64>, window: usize, align: Align) -> Vec<f64> {
+    let offset = match align {
+        Align::Right => 0,
+        Align::Center => (window - 1) / 2,
+        Align::Left => window - 1,
+    };
+    (0..ends.len())
+        .map(|i| ends.get(i + offset).copied().unwrap_or(f64::NAN))
+        .collect()
+}
+
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests {
     use super::*;
 
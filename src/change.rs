@@ -0,0 +1,265 @@
+use crate::error::{Result, StatsError};
+use crate::incr::Stats;
+
+//
+// Change detection
+//
+// These detectors consume values one at a time, like the incremental stats, and signal when the
+// distribution shifts. Each keeps an incr Stats of the current segment, the values since the last
+// change, and restarts it when a change is detected so that the new segment's baseline is learned
+// from scratch.
+//
+// Times count every value given to the detector, starting at 1.
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Direction {
+    Increase,
+    Decrease,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Change {
+    // The time of the value that signalled the change.
+    pub time: u32,
+    // The estimated time of the first value after the change.
+    pub start: u32,
+    pub direction: Direction,
+}
+
+// What a CUSUM detector watches for a shift in.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Monitor {
+    Mean,
+    Variance,
+}
+
+// Two-sided tabular CUSUM. The first values of each segment, at least `warmup` of them and enough
+// to have some variance, set a baseline mean and standard deviation. Later values are standardized
+// against the baseline and accumulated in upper and lower sums, less an allowed drift, and a change
+// is signalled when either sum exceeds the threshold. The drift and threshold are in standard
+// deviations; 0.5 and 5 detect a shift of one standard deviation quickly with few false alarms.
+// For the variance, the sums accumulate the squared standardized values less their expected value
+// of 1.
+pub struct Cusum {
+    monitor: Monitor,
+    warmup: u32,
+    drift: f64,
+    threshold: f64,
+    stats: Stats,
+    baseline: Option<(f64, f64)>,
+    time: u32,
+    upper: f64,
+    upper_start: u32,
+    lower: f64,
+    lower_start: u32,
+}
+
+impl Cusum {
+    pub fn new(monitor: Monitor, warmup: u32, drift: f64, threshold: f64) -> Result<Self> {
+        if warmup < 2 || !(drift >= 0.0 && f64::is_finite(drift)) {
+            return Err(StatsError::InvalidParameter);
+        }
+        if !(threshold > 0.0 && f64::is_finite(threshold)) {
+            return Err(StatsError::InvalidParameter);
+        }
+        Ok(Cusum {
+            monitor,
+            warmup,
+            drift,
+            threshold,
+            stats: Stats::new(),
+            baseline: None,
+            time: 0,
+            upper: 0.0,
+            upper_start: 0,
+            lower: 0.0,
+            lower_start: 0,
+        })
+    }
+
+    // Update the detector with the given value, returning the change if one is detected.
+    pub fn update(&mut self, x: f64) -> Result<Option<Change>> {
+        if f64::is_nan(x) || f64::is_infinite(x) {
+            return Err(StatsError::InvalidData);
+        }
+        self.time += 1;
+        self.stats.update(x)?;
+        let Some((mean, sd)) = self.baseline else {
+            if self.stats.count() >= self.warmup {
+                let sd = self.stats.sample_standard_deviation()?;
+                if sd > 0.0 {
+                    self.baseline = Some((self.stats.mean()?, sd));
+                }
+            }
+            return Ok(None);
+        };
+
+        let z = (x - mean) / sd;
+        let s = match self.monitor {
+            Monitor::Mean => z,
+            Monitor::Variance => z * z - 1.0,
+        };
+        // A sum that starts rising from zero marks the possible start of a change.
+        if self.upper == 0.0 {
+            self.upper_start = self.time;
+        }
+        if self.lower == 0.0 {
+            self.lower_start = self.time;
+        }
+        self.upper = f64::max(0.0, self.upper + s - self.drift);
+        self.lower = f64::max(0.0, self.lower - s - self.drift);
+
+        let change = if self.upper > self.threshold {
+            Some(Change {
+                time: self.time,
+                start: self.upper_start,
+                direction: Direction::Increase,
+            })
+        } else if self.lower > self.threshold {
+            Some(Change {
+                time: self.time,
+                start: self.lower_start,
+                direction: Direction::Decrease,
+            })
+        } else {
+            None
+        };
+        if change.is_some() {
+            self.reset();
+        }
+        Ok(change)
+    }
+
+    // Start a new segment, with a new baseline from the next values.
+    pub fn reset(&mut self) {
+        self.stats = Stats::new();
+        self.baseline = None;
+        self.upper = 0.0;
+        self.lower = 0.0;
+    }
+
+    // The stats of the current segment.
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    // The number of values the detector has been updated with.
+    pub fn time(&self) -> u32 {
+        self.time
+    }
+
+    // The baseline mean and standard deviation of the current segment, once the warmup is over.
+    pub fn baseline(&self) -> Option<(f64, f64)> {
+        self.baseline
+    }
+}
+
+// The Page-Hinkley test for a shift in the mean. It accumulates the deviations of the values from
+// the running mean of the segment, less a tolerance delta, and signals a change when the sum rises
+// more than the threshold lambda above its minimum, or falls more than lambda below its maximum.
+// delta and lambda are in the units of the data. No change is signalled until the segment has at
+// least `warmup` values.
+pub struct PageHinkley {
+    warmup: u32,
+    delta: f64,
+    lambda: f64,
+    stats: Stats,
+    time: u32,
+    upper: f64,
+    upper_min: f64,
+    upper_start: u32,
+    lower: f64,
+    lower_min: f64,
+    lower_start: u32,
+}
+
+impl PageHinkley {
+    pub fn new(warmup: u32, delta: f64, lambda: f64) -> Result<Self> {
+        if !(delta >= 0.0 && f64::is_finite(delta)) {
+            return Err(StatsError::InvalidParameter);
+        }
+        if !(lambda > 0.0 && f64::is_finite(lambda)) {
+            return Err(StatsError::InvalidParameter);
+        }
+        Ok(PageHinkley {
+            warmup,
+            delta,
+            lambda,
+            stats: Stats::new(),
+            time: 0,
+            upper: 0.0,
+            upper_min: 0.0,
+            upper_start: 1,
+            lower: 0.0,
+            lower_min: 0.0,
+            lower_start: 1,
+        })
+    }
+
+    // Update the detector with the given value, returning the change if one is detected.
+    pub fn update(&mut self, x: f64) -> Result<Option<Change>> {
+        if f64::is_nan(x) || f64::is_infinite(x) {
+            return Err(StatsError::InvalidData);
+        }
+        self.time += 1;
+        self.stats.update(x)?;
+        let mean = self.stats.mean()?;
+
+        // The sums are tracked from their extremes, and the value after an extreme is the
+        // estimated start of a change.
+        self.upper += x - mean - self.delta;
+        if self.upper < self.upper_min {
+            self.upper_min = self.upper;
+            self.upper_start = self.time + 1;
+        }
+        self.lower += mean - x - self.delta;
+        if self.lower < self.lower_min {
+            self.lower_min = self.lower;
+            self.lower_start = self.time + 1;
+        }
+
+        if self.stats.count() < self.warmup {
+            return Ok(None);
+        }
+        let change = if self.upper - self.upper_min > self.lambda {
+            Some(Change {
+                time: self.time,
+                start: self.upper_start,
+                direction: Direction::Increase,
+            })
+        } else if self.lower - self.lower_min > self.lambda {
+            Some(Change {
+                time: self.time,
+                start: self.lower_start,
+                direction: Direction::Decrease,
+            })
+        } else {
+            None
+        };
+        if change.is_some() {
+            self.reset();
+        }
+        Ok(change)
+    }
+
+    // Start a new segment.
+    pub fn reset(&mut self) {
+        self.stats = Stats::new();
+        self.upper = 0.0;
+        self.upper_min = 0.0;
+        self.upper_start = self.time + 1;
+        self.lower = 0.0;
+        self.lower_min = 0.0;
+        self.lower_start = self.time + 1;
+    }
+
+    // The stats of the current segment.
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    // The number of values the detector has been updated with.
+    pub fn time(&self) -> u32 {
+        self.time
+    }
+}
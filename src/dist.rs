@@ -0,0 +1,553 @@
+use crate::error::{Result, StatsError};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::float::Float;
+use core::f64::consts::PI;
+
+//
+// Probability distributions
+//
+// These are the cumulative distribution and quantile functions needed to turn the descriptive
+// statistics into confidence intervals and p-values. They're implemented here, from the special
+// functions up, so that the crate doesn't need a dependency on a numerical library. The results
+// match R and Octave to roughly 13 significant digits for the parameters found in practice.
+
+// The relative accuracy targeted by the iterative algorithms below.
+const EPS: f64 = 1e-15;
+
+// The smallest representable value used to protect the continued fractions from division by zero.
+const FPMIN: f64 = f64::MIN_POSITIVE / EPS;
+
+// The iteration limit for the series, continued fractions, and root finding.
+const MAX_ITER: u32 = 100_000;
+
+// Natural log of the gamma function for x > 0, via the Lanczos approximation (g = 7, n = 9).
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEF: [f64; 9] = [
+        0.9999999999998099,
+        676.5203681218851,
+        -1259.1392167224028,
+        771.3234287776531,
+        -176.6150291621406,
+        12.507343278686905,
+        -0.13857109526572012,
+        9.984369578019572e-6,
+        1.5056327351493116e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula.
+        return f64::ln(PI / f64::sin(PI * x)) - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let mut a = COEF[0];
+    let t = x + G + 0.5;
+    for (i, c) in COEF.iter().enumerate().skip(1) {
+        a += c / (x + i as f64);
+    }
+    0.5 * f64::ln(2.0 * PI) + (x + 0.5) * f64::ln(t) - t + f64::ln(a)
+}
+
+// The error of Stirling's approximation to ln(n!), ie ln(n!) - ((n + 1/2)ln(n) - n + ln(sqrt(2 pi))).
+// Computing it directly, rather than as the difference of two large logs, is what keeps the
+// densities below accurate for large parameters.
+fn stirling_error(n: f64) -> f64 {
+    const S0: f64 = 1.0 / 12.0;
+    const S1: f64 = 1.0 / 360.0;
+    const S2: f64 = 1.0 / 1260.0;
+    const S3: f64 = 1.0 / 1680.0;
+    const S4: f64 = 1.0 / 1188.0;
+    if n <= 15.0 {
+        return ln_gamma(n + 1.0) - (n + 0.5) * f64::ln(n) + n - 0.5 * f64::ln(2.0 * PI);
+    }
+    let nn = n * n;
+    (S0 - (S1 - (S2 - (S3 - S4 / nn) / nn) / nn) / nn) / n
+}
+
+// The deviance term x ln(x/m) + m - x, computed with a series when x is close to m to avoid
+// cancellation.
+fn deviance(x: f64, m: f64) -> f64 {
+    if f64::abs(x - m) < 0.1 * (x + m) {
+        let v = (x - m) / (x + m);
+        let mut s = (x - m) * v;
+        let mut ej = 2.0 * x * v;
+        for j in 1..MAX_ITER {
+            ej *= v * v;
+            let s1 = s + ej / (2 * j + 1) as f64;
+            if s1 == s {
+                return s1;
+            }
+            s = s1;
+        }
+        return s;
+    }
+    x * f64::ln(x / m) + m - x
+}
+
+// x^a e^(-x) / Gamma(a), the prefactor of the incomplete gamma function.
+fn gamma_prefactor(a: f64, x: f64) -> f64 {
+    a * f64::exp(-stirling_error(a) - deviance(a, x)) / f64::sqrt(2.0 * PI * a)
+}
+
+// x^a y^b / B(a, b) where y = 1 - x, the prefactor of the incomplete beta function.
+fn beta_prefactor(a: f64, b: f64, x: f64, y: f64) -> f64 {
+    let n = a + b;
+    let lc = stirling_error(n)
+        - stirling_error(a)
+        - stirling_error(b)
+        - deviance(a, n * x)
+        - deviance(b, n * y);
+    f64::exp(lc) * f64::sqrt(a * b / (2.0 * PI * n))
+}
+
+// Regularized lower incomplete gamma function P(a, x), by its series expansion. Converges quickly
+// for x < a + 1.
+fn gamma_p_series(a: f64, x: f64) -> f64 {
+    let mut ap = a;
+    let mut del = 1.0 / a;
+    let mut sum = del;
+    for _ in 0..MAX_ITER {
+        ap += 1.0;
+        del *= x / ap;
+        sum += del;
+        if f64::abs(del) < f64::abs(sum) * EPS {
+            break;
+        }
+    }
+    sum * gamma_prefactor(a, x)
+}
+
+// Regularized upper incomplete gamma function Q(a, x), by its continued fraction using the
+// modified Lentz method. Converges quickly for x >= a + 1.
+fn gamma_q_continued_fraction(a: f64, x: f64) -> f64 {
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / FPMIN;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..MAX_ITER {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if f64::abs(d) < FPMIN {
+            d = FPMIN;
+        }
+        c = b + an / c;
+        if f64::abs(c) < FPMIN {
+            c = FPMIN;
+        }
+        d = 1.0 / d;
+        let del = d * c;
+        h *= del;
+        if f64::abs(del - 1.0) < EPS {
+            break;
+        }
+    }
+    gamma_prefactor(a, x) * h
+}
+
+// Regularized lower incomplete gamma function P(a, x).
+pub(crate) fn gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x < a + 1.0 {
+        gamma_p_series(a, x)
+    } else {
+        1.0 - gamma_q_continued_fraction(a, x)
+    }
+}
+
+// Regularized upper incomplete gamma function Q(a, x) = 1 - P(a, x), computed directly so that
+// small upper tails keep their precision.
+pub(crate) fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    if x < a + 1.0 {
+        1.0 - gamma_p_series(a, x)
+    } else {
+        gamma_q_continued_fraction(a, x)
+    }
+}
+
+// The continued fraction for the incomplete beta function, using the modified Lentz method.
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let qab = a + b;
+    let qap = a + 1.0;
+    let qam = a - 1.0;
+    let mut c = 1.0;
+    let mut d = 1.0 - qab * x / qap;
+    if f64::abs(d) < FPMIN {
+        d = FPMIN;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..MAX_ITER {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        // The even step of the recurrence.
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 + aa * d;
+        if f64::abs(d) < FPMIN {
+            d = FPMIN;
+        }
+        c = 1.0 + aa / c;
+        if f64::abs(c) < FPMIN {
+            c = FPMIN;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+        // The odd step of the recurrence.
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 + aa * d;
+        if f64::abs(d) < FPMIN {
+            d = FPMIN;
+        }
+        c = 1.0 + aa / c;
+        if f64::abs(c) < FPMIN {
+            c = FPMIN;
+        }
+        d = 1.0 / d;
+        let del = d * c;
+        h *= del;
+        if f64::abs(del - 1.0) < EPS {
+            break;
+        }
+    }
+    h
+}
+
+// Regularized incomplete beta function I_x(a, b), where y = 1 - x. Both are passed so that callers
+// that can compute 1 - x without cancellation don't lose precision.
+pub(crate) fn beta_reg(a: f64, b: f64, x: f64, y: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if y <= 0.0 {
+        return 1.0;
+    }
+    let front = beta_prefactor(a, b, x, y);
+    // The continued fraction converges rapidly only on one side of the mean, so use the symmetry
+    // I_x(a, b) = 1 - I_y(b, a) on the other.
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, y) / b
+    }
+}
+
+// Find x in [lo, hi] such that f(x) = 0, given f's derivative and a bracketing interval in which
+// f is increasing. Newton steps are used when they stay inside the bracket, and bisection steps
+// otherwise, so the search always converges.
+fn find_root(f: impl Fn(f64) -> f64, df: impl Fn(f64) -> f64, lo: f64, hi: f64, x0: f64) -> f64 {
+    let (mut lo, mut hi) = (lo, hi);
+    let mut x = x0;
+    for _ in 0..MAX_ITER {
+        let fx = f(x);
+        if fx == 0.0 {
+            return x;
+        }
+        if fx < 0.0 {
+            lo = x;
+        } else {
+            hi = x;
+        }
+        let newton = x - fx / df(x);
+        let next = if newton > lo && newton < hi && newton.is_finite() {
+            newton
+        } else {
+            0.5 * (lo + hi)
+        };
+        if f64::abs(next - x) <= EPS * f64::abs(next) || hi - lo <= EPS * f64::abs(lo + hi) {
+            return next;
+        }
+        x = next;
+    }
+    x
+}
+
+fn validate_probability(p: f64) -> Result<()> {
+    if !(p > 0.0 && p < 1.0) {
+        return Err(StatsError::InvalidParameter);
+    }
+    Ok(())
+}
+
+fn validate_degrees_of_freedom(df: f64) -> Result<()> {
+    if !(df > 0.0 && df.is_finite()) {
+        return Err(StatsError::InvalidParameter);
+    }
+    Ok(())
+}
+
+// The probability that a standard normal value is less than -|x|, ie a single tail.
+pub(crate) fn normal_tail(x: f64) -> f64 {
+    0.5 * gamma_q(0.5, 0.5 * x * x)
+}
+
+// Standard normal cumulative distribution function:
+// R: pnorm(x)
+// Octave: normcdf(x)
+pub fn normal_cdf(x: f64) -> Result<f64> {
+    if f64::is_nan(x) {
+        return Err(StatsError::InvalidData);
+    }
+    // The tail is Q(1/2, x^2/2) / 2, which keeps both tails accurate when reflected about 0.5.
+    let tail = normal_tail(x);
+    if x < 0.0 {
+        Ok(tail)
+    } else {
+        Ok(1.0 - tail)
+    }
+}
+
+// Standard normal quantile function (the inverse of the CDF), using Wichura's algorithm AS 241,
+// which is accurate to about 1 part in 10^16:
+// R: qnorm(p)
+// Octave: norminv(p)
+pub fn normal_quantile(p: f64) -> Result<f64> {
+    validate_probability(p)?;
+    let q = p - 0.5;
+    if f64::abs(q) <= 0.425 {
+        let r = 0.180625 - q * q;
+        return Ok(q
+            * (((((((r * 2509.0809287301227 + 33430.57558358813) * r + 67265.7709270087) * r
+                + 45921.95393154987)
+                * r
+                + 13731.69376550946)
+                * r
+                + 1971.5909503065513)
+                * r
+                + 133.14166789178438)
+                * r
+                + 3.3871328727963665)
+            / (((((((r * 5226.495278852545 + 28729.085735721943) * r + 39307.89580009271)
+                * r
+                + 21213.794301586597)
+                * r
+                + 5394.196021424751)
+                * r
+                + 687.1870074920579)
+                * r
+                + 42.31333070160091)
+                * r
+                + 1.0));
+    }
+    let r = if q < 0.0 { p } else { 1.0 - p };
+    let r = f64::sqrt(-f64::ln(r));
+    let x = if r <= 5.0 {
+        let r = r - 1.6;
+        (((((((r * 7.745450142783414e-4 + 0.022723844989269184) * r + 0.2417807251774506) * r
+            + 1.2704582524523684)
+            * r
+            + 3.6478483247632045)
+            * r
+            + 5.769497221460691)
+            * r
+            + 4.630337846156546)
+            * r
+            + 1.4234371107496835)
+            / (((((((r * 1.0507500716444169e-9 + 5.475938084995345e-4) * r
+                + 0.015198666563616457)
+                * r
+                + 0.14810397642748008)
+                * r
+                + 0.6897673349851)
+                * r
+                + 1.6763848301838038)
+                * r
+                + 2.053191626637759)
+                * r
+                + 1.0)
+    } else {
+        let r = r - 5.0;
+        (((((((r * 2.0103343992922881e-7 + 2.7115555687434876e-5) * r + 0.0012426609473880784)
+            * r
+            + 0.026532189526576124)
+            * r
+            + 0.2965605718285049)
+            * r
+            + 1.7848265399172913)
+            * r
+            + 5.463784911164114)
+            * r
+            + 6.657904643501103)
+            / (((((((r * 2.0442631033899397e-15 + 1.421511758316446e-7) * r
+                + 1.8463183175100548e-5)
+                * r
+                + 7.868691311456133e-4)
+                * r
+                + 0.014875361290850615)
+                * r
+                + 0.1369298809227358)
+                * r
+                + 0.599832206555888)
+                * r
+                + 1.0)
+    };
+    Ok(if q < 0.0 { -x } else { x })
+}
+
+fn students_t_pdf(t: f64, df: f64) -> f64 {
+    f64::exp(
+        ln_gamma(0.5 * (df + 1.0))
+            - ln_gamma(0.5 * df)
+            - 0.5 * f64::ln(df * PI)
+            - 0.5 * (df + 1.0) * f64::ln_1p(t * t / df),
+    )
+}
+
+// The probability that a t-distributed value is less than -|t|, ie a single tail.
+pub(crate) fn students_t_tail(t: f64, df: f64) -> f64 {
+    let t2 = t * t;
+    0.5 * beta_reg(0.5 * df, 0.5, df / (df + t2), t2 / (df + t2))
+}
+
+// Student's t cumulative distribution function:
+// R: pt(t, df)
+// Octave: tcdf(t, df)
+pub fn students_t_cdf(t: f64, df: f64) -> Result<f64> {
+    validate_degrees_of_freedom(df)?;
+    if f64::is_nan(t) {
+        return Err(StatsError::InvalidData);
+    }
+    let tail = students_t_tail(t, df);
+    if t < 0.0 {
+        Ok(tail)
+    } else {
+        Ok(1.0 - tail)
+    }
+}
+
+// Student's t quantile function (the inverse of the CDF):
+// R: qt(p, df)
+// Octave: tinv(p, df)
+pub fn students_t_quantile(p: f64, df: f64) -> Result<f64> {
+    validate_probability(p)?;
+    validate_degrees_of_freedom(df)?;
+    // Solve in the lower tail, where the tail probability is computed without cancellation, then
+    // reflect. One and two degrees of freedom have closed forms.
+    let q = f64::min(p, 1.0 - p);
+    let t = if df == 1.0 {
+        -1.0 / f64::tan(PI * q)
+    } else if df == 2.0 {
+        -f64::sqrt(2.0) * (1.0 - 2.0 * q) / f64::sqrt(4.0 * q * (1.0 - q))
+    } else {
+        let mut lo = -1.0;
+        while students_t_tail(lo, df) > q {
+            lo *= 2.0;
+        }
+        let x0 = f64::max(normal_quantile(q)?, lo);
+        find_root(
+            |t| students_t_tail(t, df) - q,
+            |t| students_t_pdf(t, df),
+            lo,
+            0.0,
+            x0,
+        )
+    };
+    if p < 0.5 {
+        Ok(t)
+    } else {
+        Ok(-t)
+    }
+}
+
+fn chi_squared_pdf(x: f64, df: f64) -> f64 {
+    let k = 0.5 * df;
+    f64::exp((k - 1.0) * f64::ln(x) - 0.5 * x - k * f64::ln(2.0) - ln_gamma(k))
+}
+
+// Chi-squared cumulative distribution function:
+// R: pchisq(x, df)
+// Octave: chi2cdf(x, df)
+pub fn chi_squared_cdf(x: f64, df: f64) -> Result<f64> {
+    validate_degrees_of_freedom(df)?;
+    if f64::is_nan(x) {
+        return Err(StatsError::InvalidData);
+    }
+    Ok(gamma_p(0.5 * df, 0.5 * x))
+}
+
+// The probability that a chi-squared value exceeds x, ie the upper tail, used for p-values.
+pub(crate) fn chi_squared_tail(x: f64, df: f64) -> f64 {
+    gamma_q(0.5 * df, 0.5 * x)
+}
+
+// Chi-squared quantile function (the inverse of the CDF):
+// R: qchisq(p, df)
+// Octave: chi2inv(p, df)
+pub fn chi_squared_quantile(p: f64, df: f64) -> Result<f64> {
+    validate_probability(p)?;
+    validate_degrees_of_freedom(df)?;
+    let mut hi = f64::max(df, 1.0);
+    while gamma_p(0.5 * df, 0.5 * hi) < p {
+        hi *= 2.0;
+    }
+    // Work with whichever tail is smaller so that it keeps its precision.
+    let x = if p < 0.5 {
+        find_root(
+            |x| gamma_p(0.5 * df, 0.5 * x) - p,
+            |x| chi_squared_pdf(x, df),
+            0.0,
+            hi,
+            0.5 * hi,
+        )
+    } else {
+        find_root(
+            |x| (1.0 - p) - gamma_q(0.5 * df, 0.5 * x),
+            |x| chi_squared_pdf(x, df),
+            0.0,
+            hi,
+            0.5 * hi,
+        )
+    };
+    Ok(x)
+}
+
+// The lower and upper tails of the F distribution at x, ie P(X <= x) and P(X > x). Both are
+// computed directly so that neither loses precision.
+pub(crate) fn f_tails(x: f64, df1: f64, df2: f64) -> (f64, f64) {
+    if x <= 0.0 {
+        return (0.0, 1.0);
+    }
+    let u = df1 * x / (df1 * x + df2);
+    let v = df2 / (df1 * x + df2);
+    (
+        beta_reg(0.5 * df1, 0.5 * df2, u, v),
+        beta_reg(0.5 * df2, 0.5 * df1, v, u),
+    )
+}
+
+// F cumulative distribution function:
+// R: pf(x, df1, df2)
+// Octave: fcdf(x, df1, df2)
+pub fn f_cdf(x: f64, df1: f64, df2: f64) -> Result<f64> {
+    validate_degrees_of_freedom(df1)?;
+    validate_degrees_of_freedom(df2)?;
+    if f64::is_nan(x) {
+        return Err(StatsError::InvalidData);
+    }
+    Ok(f_tails(x, df1, df2).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // To reduce the file sizes, the unit tests are split into separate files.
+    // Private functions are tested here because child modules have access to
+    // private data. The public functions are tested in the `./tests` directory.
+
+    #[test]
+    fn test_special_functions() {
+        assert!(f64::abs(ln_gamma(1.0)) < 1e-15);
+        assert!(f64::abs(ln_gamma(2.0)) < 1e-15);
+        assert!(f64::abs(ln_gamma(0.5) - f64::ln(f64::sqrt(PI))) < 1e-15);
+        assert!(f64::abs(ln_gamma(11.0) - f64::ln(3628800.0)) < 1e-13);
+        assert!(f64::abs(gamma_p(1.0, 1.0) - (1.0 - f64::exp(-1.0))) < 1e-15);
+        assert!(f64::abs(gamma_q(1.0, 5.0) - f64::exp(-5.0)) < 1e-15);
+        assert!(f64::abs(beta_reg(1.0, 1.0, 0.3, 0.7) - 0.3) < 1e-15);
+        assert!(f64::abs(beta_reg(2.0, 3.0, 0.4, 0.6) - 0.5248) < 1e-14);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::batch;
+use crate::error::{Result, StatsError};
+use alloc::vec::Vec;
+
+//
+// Empirical distribution function
+//
+// The Ecdf keeps a sorted copy of the data, so that each lookup is a binary search rather than a
+// pass over the data.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ecdf {
+    sorted: Vec<f64>,
+    mean: f64,
+    sample_standard_deviation: Option<f64>,
+}
+
+impl Ecdf {
+    pub fn new(data: &[f64]) -> Result<Self> {
+        batch::validate(data)?;
+        let mut sorted = data.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        Ok(Ecdf {
+            mean: batch::mean(&sorted)?,
+            sample_standard_deviation: batch::sample_standard_deviation(&sorted).ok(),
+            sorted,
+        })
+    }
+
+    pub fn count(&self) -> u32 {
+        self.sorted.len() as u32
+    }
+
+    // The sorted data.
+    pub fn values(&self) -> &[f64] {
+        &self.sorted
+    }
+
+    // The fraction of the values that are less than or equal to x.
+    // R: ecdf(a)(x)
+    pub fn cdf(&self, x: f64) -> Result<f64> {
+        if f64::is_nan(x) {
+            return Err(StatsError::InvalidData);
+        }
+        Ok(self.count_at_most(x) as f64 / self.sorted.len() as f64)
+    }
+
+    // The p quantile, interpolating linearly between the sorted values.
+    // R: quantile(a, p)
+    // Octave: quantile(a, p, 1, 7)
+    pub fn quantile(&self, p: f64) -> Result<f64> {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(StatsError::InvalidParameter);
+        }
+        Ok(batch::sorted_quantile(&self.sorted, p))
+    }
+
+    // The percentage of the values that are below x, counting values equal to x as half below.
+    // R: 100 * (ecdf(a)(x) + mean(a < x)) / 2
+    pub fn percentile_rank(&self, x: f64) -> Result<f64> {
+        if f64::is_nan(x) {
+            return Err(StatsError::InvalidData);
+        }
+        let below = self.sorted.partition_point(|v| *v < x);
+        let at_most = self.count_at_most(x);
+        Ok(50.0 * (below + at_most) as f64 / self.sorted.len() as f64)
+    }
+
+    // The number of sample standard deviations that x is from the mean.
+    // R: (x - mean(a)) / sd(a)
+    // Octave: (x - mean(a)) / std(a)
+    pub fn z_score(&self, x: f64) -> Result<f64> {
+        if f64::is_nan(x) || f64::is_infinite(x) {
+            return Err(StatsError::InvalidData);
+        }
+        let sd = self
+            .sample_standard_deviation
+            .ok_or(StatsError::NotEnoughData)?;
+        if sd == 0.0 {
+            return Err(StatsError::Undefined);
+        }
+        Ok((x - self.mean) / sd)
+    }
+
+    fn count_at_most(&self, x: f64) -> usize {
+        self.sorted.partition_point(|v| *v <= x)
+    }
+}
@@ -1,4 +1,4 @@
-pub type Result<T> = std::result::Result<T, StatsError>;
+pub type Result<T> = core::result::Result<T, StatsError>;
 
 #[derive(Debug, Copy, Clone, PartialEq, thiserror::Error)]
 pub enum StatsError {
@@ -8,4 +8,6 @@ pub enum StatsError {
     Undefined,
     #[error("data contains NaNs or Infs")]
     InvalidData,
+    #[error("parameter is out of range")]
+    InvalidParameter,
 }
@@ -0,0 +1,84 @@
+//
+// Floating point functions for no_std
+//
+// core doesn't have f64's transcendental functions, so without the std feature this trait supplies
+// the ones the crate uses from libm. Modules import it only for no_std builds, where the calls like
+// f64::sqrt(x) and x.sqrt() resolve to it instead of the inherent methods. When std is linked
+// anyway, as it is for the tests, its inherent methods take precedence, leaving the trait and its
+// imports unused.
+
+#[allow(dead_code)]
+pub(crate) trait Float {
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn ln_1p(self) -> Self;
+    fn log2(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn sin(self) -> Self;
+    fn tan(self) -> Self;
+    fn asin(self) -> Self;
+    fn asinh(self) -> Self;
+}
+
+impl Float for f64 {
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    fn cbrt(self) -> f64 {
+        libm::cbrt(self)
+    }
+
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+
+    fn ln_1p(self) -> f64 {
+        libm::log1p(self)
+    }
+
+    fn log2(self) -> f64 {
+        libm::log2(self)
+    }
+
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+
+    fn ceil(self) -> f64 {
+        libm::ceil(self)
+    }
+
+    fn round(self) -> f64 {
+        libm::round(self)
+    }
+
+    fn sin(self) -> f64 {
+        libm::sin(self)
+    }
+
+    fn tan(self) -> f64 {
+        libm::tan(self)
+    }
+
+    fn asin(self) -> f64 {
+        libm::asin(self)
+    }
+
+    fn asinh(self) -> f64 {
+        libm::asinh(self)
+    }
+}
@@ -0,0 +1,487 @@
+use crate::error::{Result, StatsError};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::float::Float;
+use alloc::vec;
+use alloc::vec::Vec;
+
+//
+// Streaming histograms
+//
+// A histogram counts values into buckets without storing them, so like the incremental stats it
+// uses constant memory however much data it sees. Quantiles and the CDF are approximate; within a
+// bucket the values are assumed to be spread evenly. Values outside of the layout's range are
+// counted as underflow or overflow, and the smallest and largest values seen bound those
+// buckets.
+
+// The bucket layout of a histogram.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Layout {
+    // Buckets of equal width between min and max.
+    Fixed {
+        min: f64,
+        max: f64,
+        bins: usize,
+    },
+    // Buckets whose widths grow geometrically between min and max, which must be positive. Each
+    // bucket has the same relative width.
+    Log {
+        min: f64,
+        max: f64,
+        bins: usize,
+    },
+    // HDR-style buckets between min and max, which must be positive. Each power-of-two range above
+    // min is split into equal-width buckets, enough that a bucket is no wider than 10^-digits of
+    // its lower edge, for 1 to 5 significant digits.
+    Hdr {
+        min: f64,
+        max: f64,
+        significant_digits: u32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    layout: Layout,
+    edges: Vec<f64>,
+    counts: Vec<u32>,
+    underflow: u32,
+    overflow: u32,
+    n_int: u32,
+    min: f64,
+    max: f64,
+}
+
+impl Histogram {
+    pub fn new(layout: Layout) -> Result<Self> {
+        let edges = match layout {
+            Layout::Fixed { min, max, bins } => {
+                validate_range(min, max, bins)?;
+                equal_count_edges(min, max, bins)?
+            }
+            Layout::Log { min, max, bins } => {
+                validate_range(min, max, bins)?;
+                if min <= 0.0 {
+                    return Err(StatsError::InvalidParameter);
+                }
+                let ratio = max / min;
+                let mut edges: Vec<f64> = (0..bins)
+                    .map(|i| min * f64::powf(ratio, i as f64 / bins as f64))
+                    .collect();
+                edges.push(max);
+                edges
+            }
+            Layout::Hdr {
+                min,
+                max,
+                significant_digits,
+            } => {
+                validate_range(min, max, 1)?;
+                if min <= 0.0 || !(1..=5).contains(&significant_digits) {
+                    return Err(StatsError::InvalidParameter);
+                }
+                let sub_buckets = hdr_sub_buckets(significant_digits);
+                let mut edges = Vec::new();
+                let mut base = min;
+                'octaves: loop {
+                    for i in 0..sub_buckets {
+                        let edge = base * (1.0 + i as f64 / sub_buckets as f64);
+                        if edge >= max {
+                            break 'octaves;
+                        }
+                        edges.push(edge);
+                    }
+                    base *= 2.0;
+                }
+                edges.push(max);
+                edges
+            }
+        };
+        Ok(Histogram {
+            layout,
+            counts: vec![0; edges.len() - 1],
+            edges,
+            underflow: 0,
+            overflow: 0,
+            n_int: 0,
+            min: 0.0,
+            max: 0.0,
+        })
+    }
+
+    pub fn fixed(min: f64, max: f64, bins: usize) -> Result<Self> {
+        Histogram::new(Layout::Fixed { min, max, bins })
+    }
+
+    pub fn log(min: f64, max: f64, bins: usize) -> Result<Self> {
+        Histogram::new(Layout::Log { min, max, bins })
+    }
+
+    pub fn hdr(min: f64, max: f64, significant_digits: u32) -> Result<Self> {
+        Histogram::new(Layout::Hdr {
+            min,
+            max,
+            significant_digits,
+        })
+    }
+
+    // Count the given value in its bucket.
+    pub fn update(&mut self, x: f64) -> Result<()> {
+        if f64::is_nan(x) || f64::is_infinite(x) {
+            return Err(StatsError::InvalidData);
+        }
+        self.record(x);
+        Ok(())
+    }
+
+    pub fn array_update(&mut self, data: &[f64]) -> Result<()> {
+        for v in data {
+            self.update(*v)?;
+        }
+        Ok(())
+    }
+
+    // Count a value that is known to be finite.
+    pub(crate) fn record(&mut self, x: f64) {
+        if self.n_int == 0 || x < self.min {
+            self.min = x
+        }
+        if self.n_int == 0 || x > self.max {
+            self.max = x
+        }
+        self.n_int += 1;
+        match self.bucket(x) {
+            Bucket::Underflow => self.underflow += 1,
+            Bucket::Overflow => self.overflow += 1,
+            Bucket::Index(i) => self.counts[i] += 1,
+        }
+    }
+
+    // Undo the recording of x, restoring the min and max from before it.
+    pub(crate) fn unrecord(&mut self, x: f64, min: f64, max: f64) {
+        self.n_int -= 1;
+        match self.bucket(x) {
+            Bucket::Underflow => self.underflow -= 1,
+            Bucket::Overflow => self.overflow -= 1,
+            Bucket::Index(i) => self.counts[i] -= 1,
+        }
+        self.min = min;
+        self.max = max;
+    }
+
+    // Add the counts of another histogram with the same layout.
+    pub fn merge(&mut self, other: &Histogram) -> Result<()> {
+        if self.layout != other.layout {
+            return Err(StatsError::InvalidParameter);
+        }
+        if other.n_int == 0 {
+            return Ok(());
+        }
+        if self.n_int == 0 || other.min < self.min {
+            self.min = other.min
+        }
+        if self.n_int == 0 || other.max > self.max {
+            self.max = other.max
+        }
+        self.n_int += other.n_int;
+        self.underflow += other.underflow;
+        self.overflow += other.overflow;
+        for (count, other_count) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other_count;
+        }
+        Ok(())
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    pub fn count(&self) -> u32 {
+        self.n_int
+    }
+
+    // The bucket edges, one more than the number of buckets. Bucket i holds the values from
+    // edges[i] up to but not including edges[i + 1], except that the last bucket includes its
+    // upper edge.
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    pub fn counts(&self) -> &[u32] {
+        &self.counts
+    }
+
+    // The number of values below the first edge.
+    pub fn underflow(&self) -> u32 {
+        self.underflow
+    }
+
+    // The number of values above the last edge.
+    pub fn overflow(&self) -> u32 {
+        self.overflow
+    }
+
+    pub fn min(&self) -> Result<f64> {
+        if self.n_int == 0 {
+            return Err(StatsError::NotEnoughData);
+        }
+        Ok(self.min)
+    }
+
+    pub fn max(&self) -> Result<f64> {
+        if self.n_int == 0 {
+            return Err(StatsError::NotEnoughData);
+        }
+        Ok(self.max)
+    }
+
+    // The approximate p quantile, interpolating linearly within the bucket that holds it. The 0
+    // and 1 quantiles are the smallest and largest values seen.
+    pub fn quantile(&self, p: f64) -> Result<f64> {
+        if self.n_int == 0 {
+            return Err(StatsError::NotEnoughData);
+        }
+        if !(0.0..=1.0).contains(&p) {
+            return Err(StatsError::InvalidParameter);
+        }
+        let target = p * self.n_int as f64;
+        let mut below = 0.0;
+        for (count, lower, upper) in self.buckets() {
+            let count = count as f64;
+            if count > 0.0 && below + count >= target {
+                let fraction = (target - below) / count;
+                return Ok(lower + fraction * (upper - lower));
+            }
+            below += count;
+        }
+        Ok(self.max)
+    }
+
+    // The approximate fraction of the values that are less than or equal to x, interpolating
+    // linearly within the bucket that holds x.
+    pub fn cdf(&self, x: f64) -> Result<f64> {
+        if f64::is_nan(x) {
+            return Err(StatsError::InvalidData);
+        }
+        if self.n_int == 0 {
+            return Err(StatsError::NotEnoughData);
+        }
+        let mut below = 0.0;
+        for (count, lower, upper) in self.buckets() {
+            let count = count as f64;
+            if x < upper {
+                if x > lower {
+                    below += count * (x - lower) / (upper - lower);
+                }
+                break;
+            }
+            below += count;
+        }
+        Ok(f64::min(1.0, below / self.n_int as f64))
+    }
+
+    // The count and range of each bucket in order, including underflow and overflow, with the
+    // ranges narrowed to the smallest and largest values seen.
+    fn buckets(&self) -> impl Iterator<Item = (u32, f64, f64)> + '_ {
+        let first = self.edges[0];
+        let last = self.edges[self.edges.len() - 1];
+        core::iter::once((self.underflow, self.min, first))
+            .chain(
+                self.counts
+                    .iter()
+                    .zip(self.edges.windows(2))
+                    .map(|(count, edge)| (*count, edge[0], edge[1])),
+            )
+            .chain(core::iter::once((self.overflow, last, self.max)))
+            .map(|(count, lower, upper)| {
+                let lower = f64::max(lower, self.min);
+                (count, lower, f64::max(lower, f64::min(upper, self.max)))
+            })
+    }
+
+    fn bucket(&self, x: f64) -> Bucket {
+        let last = self.edges.len() - 1;
+        if x < self.edges[0] {
+            return Bucket::Underflow;
+        }
+        if x > self.edges[last] {
+            return Bucket::Overflow;
+        }
+        // Estimate the bucket from the layout, then correct for rounding against the edges.
+        let estimate = match self.layout {
+            Layout::Fixed { min, max, bins } => (x - min) / (max - min) * bins as f64,
+            Layout::Log { min, max, bins } => f64::ln(x / min) / f64::ln(max / min) * bins as f64,
+            Layout::Hdr {
+                min,
+                significant_digits,
+                ..
+            } => {
+                let sub_buckets = hdr_sub_buckets(significant_digits) as f64;
+                let octave = f64::floor(f64::log2(x / min));
+                let base = min * f64::powf(2.0, octave);
+                octave * sub_buckets + (x / base - 1.0) * sub_buckets
+            }
+        };
+        let mut i = usize::min(estimate as usize, last - 1);
+        while i > 0 && x < self.edges[i] {
+            i -= 1;
+        }
+        while i + 1 < last && x >= self.edges[i + 1] {
+            i += 1;
+        }
+        Bucket::Index(i)
+    }
+}
+
+enum Bucket {
+    Underflow,
+    Overflow,
+    Index(usize),
+}
+
+fn validate_range(min: f64, max: f64, bins: usize) -> Result<()> {
+    if !f64::is_finite(min)
+        || !f64::is_finite(max)
+        || !f64::is_finite(max - min)
+        || min >= max
+        || bins == 0
+    {
+        return Err(StatsError::InvalidParameter);
+    }
+    Ok(())
+}
+
+// The number of buckets in each power-of-two range of an HDR layout.
+fn hdr_sub_buckets(significant_digits: u32) -> usize {
+    usize::pow(10, significant_digits).next_power_of_two()
+}
+
+//
+// Batch histogram binning
+//
+// These choose the bins for `batch::histogram()` and `vec::Stats::histogram()`, following numpy's
+// histogram(). The rules pick a bin width from the data, then use as many bins of that width as
+// are needed to span it.
+
+// How to choose the bins of a batch histogram.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Bins<'a> {
+    // This many bins of equal width between the smallest and largest values.
+    Count(usize),
+    // The given bin edges, which must be increasing. Values outside of them are not counted.
+    Edges(&'a [f64]),
+    // Equal-width bins chosen by a rule.
+    Rule(BinRule),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BinRule {
+    // The smaller of the Sturges and Freedman-Diaconis widths, or Sturges if the IQR is zero.
+    Auto,
+    // log2(n) + 1 bins. Best for small, roughly normal data.
+    Sturges,
+    // A width of 3.5 standard deviations / n^(1/3), with the population standard deviation.
+    Scott,
+    // A width of 2 IQR / n^(1/3), which is robust to outliers.
+    FreedmanDiaconis,
+    // sqrt(n) bins.
+    Sqrt,
+    // Sturges's rule with extra bins for skewed data.
+    Doane,
+}
+
+pub(crate) fn sturges_width(n: f64, range: f64) -> f64 {
+    range / (f64::log2(n) + 1.0)
+}
+
+pub(crate) fn sqrt_width(n: f64, range: f64) -> f64 {
+    range / f64::sqrt(n)
+}
+
+pub(crate) fn scott_width(n: f64, standard_deviation: f64) -> f64 {
+    f64::cbrt(24.0 * f64::sqrt(core::f64::consts::PI) / n) * standard_deviation
+}
+
+pub(crate) fn freedman_diaconis_width(n: f64, interquartile_range: f64) -> f64 {
+    2.0 * interquartile_range / f64::cbrt(n)
+}
+
+pub(crate) fn auto_width(n: f64, range: f64, interquartile_range: f64) -> f64 {
+    let fd = freedman_diaconis_width(n, interquartile_range);
+    let sturges = sturges_width(n, range);
+    if fd > 0.0 {
+        f64::min(fd, sturges)
+    } else {
+        sturges
+    }
+}
+
+// Doane's rule, given the population skewness. At least 3 values are needed.
+pub(crate) fn doane_width(n: f64, range: f64, skewness: f64) -> f64 {
+    if n <= 2.0 {
+        return 0.0;
+    }
+    let sd_skewness = f64::sqrt(6.0 * (n - 2.0) / ((n + 1.0) * (n + 3.0)));
+    range / (1.0 + f64::log2(n) + f64::log2(1.0 + f64::abs(skewness) / sd_skewness))
+}
+
+// The edges of equal-width bins between min and max, using as many bins of the given width as
+// are needed, or a single bin if the width is zero. If min and max are equal, the bins span a
+// range of 1 around them. The edges are undefined if the range or width overflows.
+pub(crate) fn equal_width_edges(min: f64, max: f64, width: f64) -> Result<Vec<f64>> {
+    if !f64::is_finite(width) || width < 0.0 {
+        return Err(StatsError::Undefined);
+    }
+    let bins = if width > 0.0 {
+        let bins = f64::ceil((max - min) / width);
+        if !f64::is_finite(bins) {
+            return Err(StatsError::Undefined);
+        }
+        bins as usize
+    } else {
+        1
+    };
+    equal_count_edges(min, max, bins)
+}
+
+pub(crate) fn equal_count_edges(min: f64, max: f64, bins: usize) -> Result<Vec<f64>> {
+    let (min, max) = if min == max {
+        (min - 0.5, max + 0.5)
+    } else {
+        (min, max)
+    };
+    let width = (max - min) / bins as f64;
+    if bins == 0 || !f64::is_finite(width) {
+        return Err(StatsError::Undefined);
+    }
+    let mut edges: Vec<f64> = (0..bins).map(|i| min + width * i as f64).collect();
+    edges.push(max);
+    Ok(edges)
+}
+
+pub(crate) fn validate_edges(edges: &[f64]) -> Result<()> {
+    if edges.len() < 2
+        || edges.iter().any(|e| !f64::is_finite(*e))
+        || edges.windows(2).any(|w| w[0] >= w[1])
+    {
+        return Err(StatsError::InvalidParameter);
+    }
+    Ok(())
+}
+
+// Count the values into the bins between the edges. As for a streaming histogram, each bin
+// includes its lower edge, and the last bin also includes its upper edge.
+pub(crate) fn bin_counts(data: &[f64], edges: &[f64]) -> Vec<u32> {
+    let last = edges.len() - 1;
+    let mut counts = vec![0; last];
+    for x in data {
+        if !(edges[0]..=edges[last]).contains(x) {
+            continue;
+        }
+        let i = usize::min(edges.partition_point(|e| e <= x), last);
+        if i >= 1 {
+            counts[i - 1] += 1;
+        }
+    }
+    counts
+}
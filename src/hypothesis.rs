@@ -0,0 +1,455 @@
+#[cfg(feature = "alloc")]
+use crate::batch;
+use crate::dist;
+use crate::error::{Result, StatsError};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::float::Float;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+//
+// Hypothesis tests
+//
+// These functions turn descriptive statistics into test statistics and p-values. The p-value is
+// the probability, under the null hypothesis, of a statistic at least as extreme as the one
+// observed, so small p-values are evidence against the null hypothesis.
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TestResult {
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+// The result of a t-test, which also reports the degrees of freedom of the t distribution. For
+// Welch's test these are usually fractional.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TTestResult {
+    pub statistic: f64,
+    pub df: f64,
+    pub p_value: f64,
+}
+
+// The result of an F-test, with the numerator and denominator degrees of freedom.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FTestResult {
+    pub statistic: f64,
+    pub df_numerator: f64,
+    pub df_denominator: f64,
+    pub p_value: f64,
+}
+
+// The result of a one-way analysis of variance, with the sums of squares between and within the
+// groups and their degrees of freedom.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AnovaResult {
+    pub ss_between: f64,
+    pub ss_within: f64,
+    pub df_between: f64,
+    pub df_within: f64,
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+// Jarque-Bera test of normality from the count and the population skewness and excess kurtosis.
+// The statistic is asymptotically chi-squared with 2 degrees of freedom.
+pub(crate) fn jarque_bera(n: f64, skewness: f64, kurtosis: f64) -> TestResult {
+    let statistic = n / 6.0 * (skewness * skewness + 0.25 * kurtosis * kurtosis);
+    TestResult {
+        statistic,
+        p_value: dist::chi_squared_tail(statistic, 2.0),
+    }
+}
+
+// D'Agostino's K-squared test of normality from the count and the population skewness and excess
+// kurtosis. The skewness and kurtosis are each transformed to approximately standard normal
+// values, following D'Agostino (1970) and Anscombe & Glynn (1983), and the sum of their squares is
+// approximately chi-squared with 2 degrees of freedom. At least 8 values are required.
+pub(crate) fn dagostino_k_squared(n: f64, skewness: f64, kurtosis: f64) -> Result<TestResult> {
+    if n < 8.0 {
+        return Err(StatsError::NotEnoughData);
+    }
+
+    // Transform the skewness.
+    let y = skewness * f64::sqrt((n + 1.0) * (n + 3.0) / (6.0 * (n - 2.0)));
+    let beta2 = 3.0 * (n * n + 27.0 * n - 70.0) * (n + 1.0) * (n + 3.0)
+        / ((n - 2.0) * (n + 5.0) * (n + 7.0) * (n + 9.0));
+    let w2 = -1.0 + f64::sqrt(2.0 * (beta2 - 1.0));
+    let delta = 1.0 / f64::sqrt(0.5 * f64::ln(w2));
+    let alpha = f64::sqrt(2.0 / (w2 - 1.0));
+    let z_skewness = delta * f64::asinh(y / alpha);
+
+    // Transform the kurtosis.
+    let b2 = kurtosis + 3.0;
+    let mean = 3.0 * (n - 1.0) / (n + 1.0);
+    let variance =
+        24.0 * n * (n - 2.0) * (n - 3.0) / ((n + 1.0) * (n + 1.0) * (n + 3.0) * (n + 5.0));
+    let x = (b2 - mean) / f64::sqrt(variance);
+    let sqrt_beta1 = 6.0 * (n * n - 5.0 * n + 2.0) / ((n + 7.0) * (n + 9.0))
+        * f64::sqrt(6.0 * (n + 3.0) * (n + 5.0) / (n * (n - 2.0) * (n - 3.0)));
+    let a = 6.0
+        + 8.0 / sqrt_beta1 * (2.0 / sqrt_beta1 + f64::sqrt(1.0 + 4.0 / (sqrt_beta1 * sqrt_beta1)));
+    let denom = 1.0 + x * f64::sqrt(2.0 / (a - 4.0));
+    if denom == 0.0 {
+        return Err(StatsError::Undefined);
+    }
+    let term = f64::signum(denom) * f64::cbrt((1.0 - 2.0 / a) / f64::abs(denom));
+    let z_kurtosis = (1.0 - 2.0 / (9.0 * a) - term) / f64::sqrt(2.0 / (9.0 * a));
+
+    let statistic = z_skewness * z_skewness + z_kurtosis * z_kurtosis;
+    Ok(TestResult {
+        statistic,
+        p_value: dist::chi_squared_tail(statistic, 2.0),
+    })
+}
+
+// Student's t-test for equal means of two samples, given each sample's count, mean, and sample
+// variance. The populations are assumed to have equal variances. The p-value is two-sided.
+pub(crate) fn students_t_test(
+    n1: f64,
+    mean1: f64,
+    variance1: f64,
+    n2: f64,
+    mean2: f64,
+    variance2: f64,
+) -> Result<TTestResult> {
+    let df = n1 + n2 - 2.0;
+    let pooled = ((n1 - 1.0) * variance1 + (n2 - 1.0) * variance2) / df;
+    let se = f64::sqrt(pooled * (1.0 / n1 + 1.0 / n2));
+    if se == 0.0 {
+        return Err(StatsError::Undefined);
+    }
+    let statistic = (mean1 - mean2) / se;
+    Ok(TTestResult {
+        statistic,
+        df,
+        p_value: 2.0 * dist::students_t_tail(statistic, df),
+    })
+}
+
+// Welch's t-test for equal means of two samples, given each sample's count, mean, and sample
+// variance. The variances may differ; the degrees of freedom are from the Welch-Satterthwaite
+// equation. The p-value is two-sided.
+pub(crate) fn welch_t_test(
+    n1: f64,
+    mean1: f64,
+    variance1: f64,
+    n2: f64,
+    mean2: f64,
+    variance2: f64,
+) -> Result<TTestResult> {
+    let se1 = variance1 / n1;
+    let se2 = variance2 / n2;
+    let se = f64::sqrt(se1 + se2);
+    if se == 0.0 {
+        return Err(StatsError::Undefined);
+    }
+    let statistic = (mean1 - mean2) / se;
+    let df = (se1 + se2) * (se1 + se2) / (se1 * se1 / (n1 - 1.0) + se2 * se2 / (n2 - 1.0));
+    Ok(TTestResult {
+        statistic,
+        df,
+        p_value: 2.0 * dist::students_t_tail(statistic, df),
+    })
+}
+
+// F-test for equal variances of two samples, given each sample's count and sample variance. The
+// statistic is the ratio of the variances. The p-value is two-sided.
+pub(crate) fn f_test(n1: f64, variance1: f64, n2: f64, variance2: f64) -> Result<FTestResult> {
+    if variance2 == 0.0 {
+        return Err(StatsError::Undefined);
+    }
+    let statistic = variance1 / variance2;
+    let df_numerator = n1 - 1.0;
+    let df_denominator = n2 - 1.0;
+    let (lower, upper) = dist::f_tails(statistic, df_numerator, df_denominator);
+    Ok(FTestResult {
+        statistic,
+        df_numerator,
+        df_denominator,
+        p_value: f64::min(1.0, 2.0 * f64::min(lower, upper)),
+    })
+}
+
+// Ljung-Box test that a series has no serial correlation, given its length and its
+// autocorrelations at lags 1 to h. The statistic is asymptotically chi-squared with h degrees of
+// freedom.
+#[cfg(feature = "alloc")]
+pub(crate) fn ljung_box(n: f64, autocorrelations: &[f64]) -> TestResult {
+    let statistic = n
+        * (n + 2.0)
+        * autocorrelations
+            .iter()
+            .enumerate()
+            .map(|(i, r)| r * r / (n - (i + 1) as f64))
+            .sum::<f64>();
+    TestResult {
+        statistic,
+        p_value: dist::chi_squared_tail(statistic, autocorrelations.len() as f64),
+    }
+}
+
+// Rank the values, giving tied values the average of the ranks they span. Also return the tie
+// correction, the sum of t^3 - t over the groups of t tied values.
+#[cfg(feature = "alloc")]
+fn average_ranks(values: &[f64]) -> (Vec<f64>, f64) {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&i, &j| f64::total_cmp(&values[i], &values[j]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut ties = 0.0;
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = 0.5 * (start + end + 1) as f64;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        let t = (end - start) as f64;
+        ties += t * t * t - t;
+        start = end;
+    }
+    (ranks, ties)
+}
+
+// The two-sided p-value of a statistic with an approximately normal distribution, including a
+// continuity correction of 1/2 towards the mean, but not past it.
+#[cfg(feature = "alloc")]
+fn normal_p_value_with_correction(statistic: f64, mean: f64, sd: f64) -> f64 {
+    let z = f64::max(f64::abs(statistic - mean) - 0.5, 0.0) / sd;
+    f64::min(1.0, 2.0 * dist::normal_tail(z))
+}
+
+// The two-sided p-value of an integer-valued statistic from its exact distribution, given as the
+// number of ways each value can occur. The distribution must be symmetric about its mean.
+#[cfg(feature = "alloc")]
+fn exact_p_value(statistic: f64, counts: &[f64]) -> f64 {
+    let total: f64 = counts.iter().sum();
+    let mean = 0.5 * (counts.len() - 1) as f64;
+    let s = statistic.round() as usize;
+    let tail: f64 = if statistic > mean {
+        counts[s..].iter().sum()
+    } else {
+        counts[..=s].iter().sum()
+    };
+    f64::min(1.0, 2.0 * tail / total)
+}
+
+// Mann-Whitney U test (aka the Wilcoxon rank-sum test) that two independent samples come from the
+// same distribution. It is a nonparametric alternative to the two-sample t-test. The statistic is
+// U for the first sample, the number of pairs in which its value is larger. The p-value is
+// two-sided; it is exact if both samples have fewer than 50 values and there are no ties, and
+// otherwise uses the normal approximation with tie and continuity corrections.
+// R: wilcox.test(x, y)
+// Octave: ranksum(x, y)
+#[cfg(feature = "alloc")]
+pub fn mann_whitney_u(x: &[f64], y: &[f64]) -> Result<TestResult> {
+    if x.is_empty() || y.is_empty() {
+        return Err(StatsError::NotEnoughData);
+    }
+    batch::validate(x)?;
+    batch::validate(y)?;
+    let (n1, n2) = (x.len(), y.len());
+    let combined: Vec<f64> = x.iter().chain(y).copied().collect();
+    let (ranks, ties) = average_ranks(&combined);
+    let (m, n) = (n1 as f64, n2 as f64);
+    let statistic = ranks[..n1].iter().sum::<f64>() - m * (m + 1.0) / 2.0;
+
+    let p_value = if n1 < 50 && n2 < 50 && ties == 0.0 {
+        // The number of ways to choose n1 of the ranks 1..=n1+n2 with each possible sum, found by
+        // adding one rank at a time. The sums are offset so that they count U directly.
+        let max_u = n1 * n2;
+        let max_sum = max_u + n1 * (n1 + 1) / 2;
+        let mut ways = vec![vec![0.0; max_sum + 1]; n1 + 1];
+        ways[0][0] = 1.0;
+        for rank in 1..=n1 + n2 {
+            for k in (1..=usize::min(rank, n1)).rev() {
+                for sum in (rank..=max_sum).rev() {
+                    ways[k][sum] += ways[k - 1][sum - rank];
+                }
+            }
+        }
+        exact_p_value(statistic, &ways[n1][n1 * (n1 + 1) / 2..])
+    } else {
+        let sd = f64::sqrt(m * n / 12.0 * ((m + n + 1.0) - ties / ((m + n) * (m + n - 1.0))));
+        if sd == 0.0 {
+            return Err(StatsError::Undefined);
+        }
+        normal_p_value_with_correction(statistic, m * n / 2.0, sd)
+    };
+    Ok(TestResult { statistic, p_value })
+}
+
+// Wilcoxon signed-rank test that the differences between paired samples are symmetric about zero.
+// It is a nonparametric alternative to the paired t-test. Pairs with equal values are dropped. The
+// statistic is the sum of the ranks of the positive differences. The p-value is two-sided; it is
+// exact if there are fewer than 50 pairs and no ties or zero differences, and otherwise uses the
+// normal approximation with tie and continuity corrections.
+// R: wilcox.test(x, y, paired = TRUE)
+// Octave: signrank(x, y)
+#[cfg(feature = "alloc")]
+pub fn wilcoxon_signed_rank(x: &[f64], y: &[f64]) -> Result<TestResult> {
+    if x.len() != y.len() {
+        return Err(StatsError::InvalidParameter);
+    }
+    batch::validate(x)?;
+    batch::validate(y)?;
+    let differences: Vec<f64> = x
+        .iter()
+        .zip(y)
+        .map(|(a, b)| a - b)
+        .filter(|d| *d != 0.0)
+        .collect();
+    if differences.is_empty() {
+        return Err(StatsError::NotEnoughData);
+    }
+    let zeros = x.len() - differences.len();
+    let magnitudes: Vec<f64> = differences.iter().map(|d| f64::abs(*d)).collect();
+    let (ranks, ties) = average_ranks(&magnitudes);
+    let statistic: f64 = ranks
+        .iter()
+        .zip(&differences)
+        .filter(|(_, d)| **d > 0.0)
+        .map(|(r, _)| r)
+        .sum();
+    let n = differences.len();
+    let nf = n as f64;
+
+    let p_value = if n < 50 && ties == 0.0 && zeros == 0 {
+        // The number of subsets of the ranks 1..=n with each possible sum.
+        let max_sum = n * (n + 1) / 2;
+        let mut ways = vec![0.0; max_sum + 1];
+        ways[0] = 1.0;
+        for rank in 1..=n {
+            for sum in (rank..=max_sum).rev() {
+                ways[sum] += ways[sum - rank];
+            }
+        }
+        exact_p_value(statistic, &ways)
+    } else {
+        let sd = f64::sqrt(nf * (nf + 1.0) * (2.0 * nf + 1.0) / 24.0 - ties / 48.0);
+        if sd == 0.0 {
+            return Err(StatsError::Undefined);
+        }
+        normal_p_value_with_correction(statistic, nf * (nf + 1.0) / 4.0, sd)
+    };
+    Ok(TestResult { statistic, p_value })
+}
+
+// Two-sample Kolmogorov-Smirnov test that two samples come from the same continuous distribution.
+// The statistic D is the largest difference between their empirical distribution functions. The
+// p-value is two-sided; it is exact if the product of the sample sizes is less than 10,000 and
+// there are no ties, and otherwise uses the asymptotic Kolmogorov distribution.
+// R: ks.test(x, y)
+// Octave: kolmogorov_smirnov_test_2(x, y)
+#[cfg(feature = "alloc")]
+pub fn kolmogorov_smirnov(x: &[f64], y: &[f64]) -> Result<TestResult> {
+    if x.is_empty() || y.is_empty() {
+        return Err(StatsError::NotEnoughData);
+    }
+    batch::validate(x)?;
+    batch::validate(y)?;
+    let mut xs = x.to_vec();
+    let mut ys = y.to_vec();
+    xs.sort_by(f64::total_cmp);
+    ys.sort_by(f64::total_cmp);
+    let (m, n) = (xs.len() as f64, ys.len() as f64);
+
+    let mut combined: Vec<f64> = xs.iter().chain(&ys).copied().collect();
+    combined.sort_by(f64::total_cmp);
+    let ties = combined.windows(2).any(|w| w[0] == w[1]);
+
+    // Walk both sorted samples, stepping past every copy of the next smallest value, and track the
+    // largest gap between the two empirical distribution functions.
+    let (mut i, mut j) = (0, 0);
+    let mut statistic: f64 = 0.0;
+    while i < xs.len() && j < ys.len() {
+        let v = f64::min(xs[i], ys[j]);
+        while i < xs.len() && xs[i] == v {
+            i += 1;
+        }
+        while j < ys.len() && ys[j] == v {
+            j += 1;
+        }
+        statistic = f64::max(statistic, f64::abs(i as f64 / m - j as f64 / n));
+    }
+
+    let p_value = if m * n < 10000.0 && !ties {
+        1.0 - smirnov_cdf(statistic, xs.len(), ys.len())
+    } else {
+        kolmogorov_tail(f64::sqrt(m * n / (m + n)) * statistic)
+    };
+    Ok(TestResult {
+        statistic,
+        p_value: p_value.clamp(0.0, 1.0),
+    })
+}
+
+// The exact probability that the two-sample statistic D is less than d, for samples of sizes m and
+// n without ties. This counts the lattice paths from (0, 0) to (m, n) that stay within d of the
+// diagonal, scaled at each step to avoid overflow.
+#[cfg(feature = "alloc")]
+fn smirnov_cdf(d: f64, m: usize, n: usize) -> f64 {
+    let (m, n) = if m > n { (n, m) } else { (m, n) };
+    let (md, nd) = (m as f64, n as f64);
+    // Move the threshold halfway between possible values of D so that rounding can't matter.
+    let q = (0.5 + f64::floor(d * md * nd - 1e-7)) / (md * nd);
+    let mut u: Vec<f64> = (0..=n)
+        .map(|j| if j as f64 / nd > q { 0.0 } else { 1.0 })
+        .collect();
+    for i in 1..=m {
+        let w = i as f64 / (i + n) as f64;
+        u[0] = if i as f64 / md > q { 0.0 } else { w * u[0] };
+        for j in 1..=n {
+            u[j] = if f64::abs(i as f64 / md - j as f64 / nd) > q {
+                0.0
+            } else {
+                w * u[j] + u[j - 1]
+            };
+        }
+    }
+    u[n]
+}
+
+// The upper tail of the Kolmogorov distribution, P(K > x).
+#[cfg(feature = "alloc")]
+fn kolmogorov_tail(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    if x < 1.0 {
+        // For small x, the alternating series converges slowly, so use the lower tail's series.
+        let z = -core::f64::consts::PI * core::f64::consts::PI / (8.0 * x * x);
+        let w = f64::ln(x);
+        let mut s = 0.0;
+        let mut k = 1.0;
+        loop {
+            let term = f64::exp(k * k * z - w);
+            s += term;
+            // For tiny x the terms underflow to zero, and the tail is 1.
+            if term == 0.0 || term < 1e-17 * s {
+                break;
+            }
+            k += 2.0;
+        }
+        return 1.0 - s * f64::sqrt(2.0 * core::f64::consts::PI);
+    }
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    let mut k = 1.0;
+    loop {
+        let term = 2.0 * f64::exp(-2.0 * k * k * x * x);
+        sum += sign * term;
+        // For large x the terms underflow to zero, and the tail is 0.
+        if term == 0.0 || term < 1e-17 * sum {
+            break;
+        }
+        sign = -sign;
+        k += 1.0;
+    }
+    sum
+}
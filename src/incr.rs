@@ -1,6 +1,40 @@
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+
+use crate::batch;
+use crate::dist;
 use crate::error::{Result, StatsError};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::float::Float;
+#[cfg(feature = "alloc")]
+use crate::hist::Histogram;
+use crate::hypothesis::{self, AnovaResult, FTestResult, TTestResult, TestResult};
+use crate::kernel;
+use crate::scale::Scaler;
+
+// The highest moment that a Stats maintains. Each includes the ones before it, and the min, max,
+// and sum are always maintained. Stats that only need the mean and variance can skip the work of
+// updating the third and fourth moments.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Moments {
+    Mean,
+    Variance,
+    Skewness,
+    #[default]
+    Kurtosis,
+}
 
-#[derive(Default)]
+// The number of values from_slice() summarizes at a time, 32 KiB of them, which fit in the L1 or L2
+// cache.
+const BLOCK_LEN: usize = 4096;
+
+#[derive(Default, Clone)]
 pub struct Stats {
     n_int: u32, // Maintain the size as an int to avoid frequent casting.
     n: f64,
@@ -11,6 +45,9 @@ pub struct Stats {
     m2: f64,
     m3: f64,
     m4: f64,
+    #[cfg(feature = "alloc")]
+    histogram: Option<Histogram>,
+    moments: Moments,
 }
 
 impl Stats {
@@ -20,6 +57,29 @@ impl Stats {
         }
     }
 
+    // Create stats that also count each value into the given histogram as they are updated.
+    #[cfg(feature = "alloc")]
+    pub fn with_histogram(histogram: Histogram) -> Self {
+        Stats {
+            histogram: Some(histogram),
+            ..Default::default()
+        }
+    }
+
+    // Create stats that only maintain the moments up to the given one. The statistics that need
+    // higher moments return InvalidParameter.
+    pub fn with_moments(moments: Moments) -> Self {
+        Stats {
+            moments,
+            ..Default::default()
+        }
+    }
+
+    // The highest moment these stats maintain.
+    pub fn moments(&self) -> Moments {
+        self.moments
+    }
+
     // Update the moments with the given value.
     pub fn update(&mut self, x: f64) -> Result<()> {
         if f64::is_nan(x) || f64::is_infinite(x) {
@@ -31,6 +91,10 @@ impl Stats {
         if self.n_int == 0 || x > self.max {
             self.max = x
         }
+        #[cfg(feature = "alloc")]
+        if let Some(histogram) = &mut self.histogram {
+            histogram.record(x);
+        }
         // Perform incremental updates from the previous values. The updates are done in careful
         // order; the values used are the prior values until they are updated.
         self.sum += x;
@@ -39,26 +103,135 @@ impl Stats {
         self.n += 1.0;
         let delta = x - self.mean; // Deviation from the prior mean.
         let delta_n = delta / self.n;
-        let delta_n2 = delta_n * delta_n;
-        let term1 = delta * delta_n * n_;
-        // Fourth moment, used to calculate kurtosis.
-        self.m4 += term1 * delta_n2 * (self.n * self.n - 3.0 * self.n + 3.0)
-            + 6.0 * delta_n2 * self.m2
-            - 4.0 * delta_n * self.m3;
-        // Third moment, used to calculate skewness.
-        self.m3 += term1 * delta_n * (self.n - 2.0) - 3.0 * delta_n * self.m2;
-        // Second moment, used to calculate variance.
-        self.m2 += term1;
+        if self.moments >= Moments::Variance {
+            let delta_n2 = delta_n * delta_n;
+            let term1 = delta * delta_n * n_;
+            if self.moments == Moments::Kurtosis {
+                // Fourth moment, used to calculate kurtosis.
+                self.m4 += term1 * delta_n2 * (self.n * self.n - 3.0 * self.n + 3.0)
+                    + 6.0 * delta_n2 * self.m2
+                    - 4.0 * delta_n * self.m3;
+            }
+            if self.moments >= Moments::Skewness {
+                // Third moment, used to calculate skewness.
+                self.m3 += term1 * delta_n * (self.n - 2.0) - 3.0 * delta_n * self.m2;
+            }
+            // Second moment, used to calculate variance.
+            self.m2 += term1;
+        }
         // First moment, the mean.
         self.mean += delta_n;
 
         Ok(())
     }
 
+    // Merge the stats of another set of values into these, giving the same stats as if these had
+    // also been updated with the other values. The moments are combined with the pairwise formulas
+    // of Chan et al. and Pebay, so stats of separate parts of the data, for example from different
+    // threads, can be calculated independently and then merged. Either both or neither of the
+    // stats must have a histogram, and the histograms must have the same layout.
+    pub fn merge(&mut self, other: &Stats) -> Result<()> {
+        #[cfg(feature = "alloc")]
+        match (&mut self.histogram, &other.histogram) {
+            (Some(histogram), Some(other_histogram)) => histogram.merge(other_histogram)?,
+            (None, None) => {}
+            _ => return Err(StatsError::InvalidParameter),
+        }
+        self.merge_moments(other);
+        Ok(())
+    }
+
+    // Check that the stats maintain the given moment.
+    fn require(&self, moments: Moments) -> Result<()> {
+        if self.moments < moments {
+            return Err(StatsError::InvalidParameter);
+        }
+        Ok(())
+    }
+
+    // Merge the moments, min, max, and sum of another stats, ignoring the histograms. The merged
+    // stats maintain the lower of the two stats' moments.
+    fn merge_moments(&mut self, other: &Stats) {
+        self.moments = Ord::min(self.moments, other.moments);
+        if other.n_int == 0 {
+            return;
+        }
+        // Copy the other stats' moments into empty stats, rather than rounding them through the
+        // pairwise formulas.
+        if self.n_int == 0 {
+            (self.n_int, self.n) = (other.n_int, other.n);
+            (self.min, self.max, self.sum) = (other.min, other.max, other.sum);
+            (self.mean, self.m2, self.m3, self.m4) = (other.mean, other.m2, other.m3, other.m4);
+            return;
+        }
+        if self.n_int == 0 || other.min < self.min {
+            self.min = other.min
+        }
+        if self.n_int == 0 || other.max > self.max {
+            self.max = other.max
+        }
+        let (na, nb) = (self.n, other.n);
+        let n = na + nb;
+        let delta = other.mean - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        // The moments are updated from the highest order down, since each uses the lower ones.
+        self.m4 += other.m4
+            + delta * delta_n2 * delta_n * na * nb * (na * na - na * nb + nb * nb)
+            + 6.0 * delta_n2 * (na * na * other.m2 + nb * nb * self.m2)
+            + 4.0 * delta_n * (na * other.m3 - nb * self.m3);
+        self.m3 += other.m3
+            + delta * delta_n2 * na * nb * (na - nb)
+            + 3.0 * delta_n * (na * other.m2 - nb * self.m2);
+        self.m2 += other.m2 + delta * delta_n * na * nb;
+        self.mean += delta_n * nb;
+        self.sum += other.sum;
+        self.n_int += other.n_int;
+        self.n = n;
+    }
+
+    // Reverse the update of the moments and sum with the given value, which must be one of the
+    // values the stats were updated with. The min, max, and histogram aren't changed, so this is
+    // only for the rolling batch functions and the fixed-capacity Window, which track the min and
+    // max themselves.
+    pub(crate) fn remove(&mut self, x: f64) {
+        let n = self.n; // Prior n.
+        self.sum -= x;
+        self.n_int -= 1;
+        self.n -= 1.0;
+        if self.n_int == 0 {
+            (self.mean, self.m2, self.m3, self.m4) = (0.0, 0.0, 0.0, 0.0);
+            return;
+        }
+        // Undo the updates in reverse order. The deviation of x from the remaining values' mean is
+        // recovered from its deviation from the prior mean, so that removing a value equal to the
+        // mean leaves the moments exactly unchanged.
+        let delta = (x - self.mean) * n / self.n;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term1 = delta * delta_n * self.n;
+        self.mean -= delta_n;
+        self.m2 = f64::max(0.0, self.m2 - term1);
+        self.m3 -= term1 * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m4 -= term1 * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2
+            - 4.0 * delta_n * self.m3;
+    }
+
     pub fn count(&self) -> u32 {
         self.n_int
     }
 
+    // The histogram given to with_histogram(), if any.
+    #[cfg(feature = "alloc")]
+    pub fn histogram(&self) -> Option<&Histogram> {
+        self.histogram.as_ref()
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn histogram_mut(&mut self) -> Option<&mut Histogram> {
+        self.histogram.as_mut()
+    }
+
     pub fn min(&self) -> Result<f64> {
         if self.n_int == 0 {
             return Err(StatsError::NotEnoughData);
@@ -97,10 +270,85 @@ impl Stats {
         Ok(())
     }
 
+    // Update the stats with a slice of values, like array_update() but about ten times faster for a
+    // slice in the cache, and five or six times for one read from memory. The moments of each block
+    // of the slice are calculated in two tight passes, as in vec::descriptive(), and merged into
+    // the stats, giving the same results within rounding. If any value is a NaN or Inf, none of
+    // them are added.
+    pub fn update_many(&mut self, data: &[f64]) -> Result<()> {
+        let stats = Stats::from_slice(data);
+        // Any NaN or Inf makes the sum NaN or infinite, so the data only needs to be checked value
+        // by value if the sum isn't finite, which could also be from very large values.
+        if !f64::is_finite(stats.sum) {
+            batch::validate(data)?;
+        }
+        #[cfg(feature = "alloc")]
+        if let Some(histogram) = &mut self.histogram {
+            data.iter().for_each(|x| histogram.record(*x));
+        }
+        self.merge_moments(&stats);
+        Ok(())
+    }
+
+    // The stats of a slice, without validating the data. The slice is summarized a block at a
+    // time, so that both passes over each block read it from the cache rather than from memory,
+    // and the blocks' stats are merged.
+    pub(crate) fn from_slice(data: &[f64]) -> Stats {
+        let mut stats = Stats::new();
+        for block in data.chunks(BLOCK_LEN) {
+            stats.merge_moments(&Stats::from_block(block));
+        }
+        stats
+    }
+
+    // The stats of a non-empty block, from the sum, min, and max and then the central power sums.
+    fn from_block(data: &[f64]) -> Stats {
+        let n = data.len() as f64;
+        let (sum, min, max) = kernel::sum_min_max(data);
+        // If the sum of finite values overflows, the mean can't be calculated from it, so update the
+        // stats one value at a time instead. A NaN or Inf still gives NaN moments.
+        if !f64::is_finite(sum) {
+            let mut stats = Stats::new();
+            if stats.array_update(data).is_ok() {
+                return stats;
+            }
+        }
+        let mean = sum / n;
+        let (m2, m3, m4) = kernel::central_power_sums(data, mean);
+        Stats {
+            n_int: data.len() as u32,
+            n,
+            min,
+            max,
+            sum,
+            mean,
+            m2,
+            m3,
+            m4,
+            #[cfg(feature = "alloc")]
+            histogram: None,
+            moments: Moments::Kurtosis,
+        }
+    }
+
+    // Update the stats with each of the values from an iterator, such as one reading from a file or
+    // channel. It stops at the first NaN or Inf, leaving the stats updated with the values before it.
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<f64>,
+    {
+        for v in iter {
+            self.update(*v.borrow())?;
+        }
+        Ok(())
+    }
+
     // Population variance:
     // R: var.pop=function(x){(length(x)-1)/length(x)*var(x)}
     // Octave: var(a, 1)
     pub fn population_variance(&self) -> Result<f64> {
+        self.require(Moments::Variance)?;
         if self.n_int == 0 || self.n_int == 1 {
             return Err(StatsError::NotEnoughData);
         }
@@ -111,6 +359,7 @@ impl Stats {
     // R: var(a)
     // Octave: var(a)
     pub fn sample_variance(&self) -> Result<f64> {
+        self.require(Moments::Variance)?;
         if self.n_int == 0 || self.n_int == 1 {
             return Err(StatsError::NotEnoughData);
         }
@@ -142,6 +391,7 @@ impl Stats {
     // or library(DescTools); Skew(a, method = 1)
     // Octave: skewness(a)
     pub fn population_skewness(&self) -> Result<f64> {
+        self.require(Moments::Skewness)?;
         if self.n_int <= 1 {
             return Err(StatsError::NotEnoughData);
         }
@@ -171,6 +421,7 @@ impl Stats {
     // or library(DescTools); Kurt(a, method = 1)
     // Octave: kurtosis(a) - 3.0
     pub fn population_kurtosis(&self) -> Result<f64> {
+        self.require(Moments::Kurtosis)?;
         if self.n_int <= 1 {
             return Err(StatsError::NotEnoughData);
         }
@@ -191,4 +442,382 @@ impl Stats {
         let k = self.population_kurtosis()?;
         Ok((self.n - 1.0) / ((self.n - 2.0) * (self.n - 3.0)) * ((self.n + 1.0) * k + 6.0))
     }
+
+    // Confidence interval for the mean, using Student's t distribution. The confidence level is a
+    // probability such as 0.95. The interval is returned as (lower, upper).
+    // R: t.test(a, conf.level = level)$conf.int
+    // Octave: [h, pval, ci] = ttest(a, 0, "alpha", 1 - level)
+    pub fn mean_confidence_interval(&self, level: f64) -> Result<(f64, f64)> {
+        if self.n_int <= 1 {
+            return Err(StatsError::NotEnoughData);
+        }
+        if !(level > 0.0 && level < 1.0) {
+            return Err(StatsError::InvalidParameter);
+        }
+        let t = dist::students_t_quantile(0.5 + 0.5 * level, self.n - 1.0)?;
+        let half_width = t * f64::sqrt(self.sample_variance()? / self.n);
+        Ok((self.mean - half_width, self.mean + half_width))
+    }
+
+    // Confidence interval for the variance, using the chi-squared distribution. This assumes the
+    // data is normally distributed.
+    // R: library(EnvStats); varTest(a, conf.level = level)$conf.int
+    pub fn variance_confidence_interval(&self, level: f64) -> Result<(f64, f64)> {
+        self.require(Moments::Variance)?;
+        if self.n_int <= 1 {
+            return Err(StatsError::NotEnoughData);
+        }
+        if !(level > 0.0 && level < 1.0) {
+            return Err(StatsError::InvalidParameter);
+        }
+        let df = self.n - 1.0;
+        let lower = dist::chi_squared_quantile(0.5 + 0.5 * level, df)?;
+        let upper = dist::chi_squared_quantile(0.5 - 0.5 * level, df)?;
+        Ok((self.m2 / lower, self.m2 / upper))
+    }
+
+    // Confidence interval for the standard deviation, the square root of the variance interval.
+    // R: library(EnvStats); sqrt(varTest(a, conf.level = level)$conf.int)
+    pub fn standard_deviation_confidence_interval(&self, level: f64) -> Result<(f64, f64)> {
+        let (lower, upper) = self.variance_confidence_interval(level)?;
+        Ok((f64::sqrt(lower), f64::sqrt(upper)))
+    }
+
+    // Jarque-Bera test of normality, based on the population skewness and kurtosis. Small p-values
+    // indicate that the data is unlikely to be normally distributed.
+    // R: library(tseries); jarque.bera.test(a)
+    pub fn jarque_bera(&self) -> Result<TestResult> {
+        Ok(hypothesis::jarque_bera(
+            self.n,
+            self.population_skewness()?,
+            self.population_kurtosis()?,
+        ))
+    }
+
+    // D'Agostino's K-squared omnibus test of normality, combining tests of the skewness and the
+    // kurtosis. Requires at least 8 values.
+    // R: library(fBasics); dagoTest(a)
+    pub fn dagostino_k_squared(&self) -> Result<TestResult> {
+        if self.n_int < 8 {
+            return Err(StatsError::NotEnoughData);
+        }
+        hypothesis::dagostino_k_squared(
+            self.n,
+            self.population_skewness()?,
+            self.population_kurtosis()?,
+        )
+    }
+
+    // Student's t-test that this sample and another have equal means, assuming that their
+    // populations have equal variances. The p-value is two-sided.
+    // R: t.test(a, b, var.equal = TRUE)
+    // Octave: ttest2(a, b)
+    pub fn students_t_test(&self, other: &Stats) -> Result<TTestResult> {
+        if self.n_int <= 1 || other.n_int <= 1 {
+            return Err(StatsError::NotEnoughData);
+        }
+        hypothesis::students_t_test(
+            self.n,
+            self.mean,
+            self.sample_variance()?,
+            other.n,
+            other.mean,
+            other.sample_variance()?,
+        )
+    }
+
+    // Welch's t-test that this sample and another have equal means, without assuming equal
+    // variances. The p-value is two-sided.
+    // R: t.test(a, b)
+    // Octave: ttest2(a, b, "vartype", "unequal")
+    pub fn welch_t_test(&self, other: &Stats) -> Result<TTestResult> {
+        if self.n_int <= 1 || other.n_int <= 1 {
+            return Err(StatsError::NotEnoughData);
+        }
+        hypothesis::welch_t_test(
+            self.n,
+            self.mean,
+            self.sample_variance()?,
+            other.n,
+            other.mean,
+            other.sample_variance()?,
+        )
+    }
+
+    // F-test that this sample and another have equal variances. The statistic is the ratio of this
+    // sample's variance to the other's. The p-value is two-sided.
+    // R: var.test(a, b)
+    // Octave: vartest2(a, b)
+    pub fn f_test(&self, other: &Stats) -> Result<FTestResult> {
+        if self.n_int <= 1 || other.n_int <= 1 {
+            return Err(StatsError::NotEnoughData);
+        }
+        hypothesis::f_test(
+            self.n,
+            self.sample_variance()?,
+            other.n,
+            other.sample_variance()?,
+        )
+    }
+
+    // A scaler to z-scores, using the mean and sample standard deviation.
+    // R: scale(a)
+    pub fn z_score_scaler(&self) -> Result<Scaler> {
+        Scaler::fit(self.mean()?, self.sample_standard_deviation()?)
+    }
+
+    // A scaler onto [0, 1], using the min and the range.
+    // R: scale(a, min(a), diff(range(a)))
+    pub fn min_max_scaler(&self) -> Result<Scaler> {
+        Scaler::fit(self.min()?, self.max()? - self.min()?)
+    }
+}
+
+// Stats can be collected from, or extended with, values that are known to be valid. try_extend()
+// and StatsExt::stats() return the error instead. Collecting an iterator of Result<f64> into a
+// Result<Stats> stops at the first Err, but is built on collect(), so it can't check the Ok values.
+// Panics: on a NaN or Inf, including an Ok(NaN) collected into a Result<Stats>.
+impl FromIterator<f64> for Stats {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut stats = Stats::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+impl<'a> FromIterator<&'a f64> for Stats {
+    fn from_iter<I: IntoIterator<Item = &'a f64>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+// Panics: on a NaN or Inf.
+impl Extend<f64> for Stats {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        if let Err(e) = self.try_extend(iter) {
+            panic!("can't extend stats: {e}");
+        }
+    }
+}
+
+impl<'a> Extend<&'a f64> for Stats {
+    fn extend<I: IntoIterator<Item = &'a f64>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+// Calculate the stats of any iterator of f64 or &f64, for example `data.iter().stats()?`.
+pub trait StatsExt: Iterator {
+    fn stats(self) -> Result<Stats>;
+}
+
+impl<I> StatsExt for I
+where
+    I: Iterator,
+    I::Item: Borrow<f64>,
+{
+    fn stats(self) -> Result<Stats> {
+        let mut stats = Stats::new();
+        stats.try_extend(self)?;
+        Ok(stats)
+    }
+}
+
+// One-way analysis of variance that several groups have equal means, assuming that their
+// populations have equal variances. Each group is summarized by its own Stats, so only the counts,
+// means, and sums of squared deviations are used. The statistic is F, the ratio of the mean squares
+// between and within the groups, and the p-value is its upper tail.
+// R: summary(aov(value ~ group))
+// Octave: anova1(values, groups)
+pub fn one_way_anova(groups: &[Stats]) -> Result<AnovaResult> {
+    if groups.len() < 2 || groups.iter().any(|g| g.n_int == 0) {
+        return Err(StatsError::NotEnoughData);
+    }
+    let n: f64 = groups.iter().map(|g| g.n).sum();
+    let k = groups.len() as f64;
+    if n <= k {
+        return Err(StatsError::NotEnoughData);
+    }
+    let grand_mean = groups.iter().map(|g| g.sum).sum::<f64>() / n;
+    let ss_between: f64 = groups
+        .iter()
+        .map(|g| g.n * (g.mean - grand_mean) * (g.mean - grand_mean))
+        .sum();
+    for g in groups {
+        g.require(Moments::Variance)?;
+    }
+    let ss_within: f64 = groups.iter().map(|g| g.m2).sum();
+    if ss_within == 0.0 {
+        return Err(StatsError::Undefined);
+    }
+    let df_between = k - 1.0;
+    let df_within = n - k;
+    let statistic = (ss_between / df_between) / (ss_within / df_within);
+    Ok(AnovaResult {
+        ss_between,
+        ss_within,
+        df_between,
+        df_within,
+        statistic,
+        p_value: dist::f_tails(statistic, df_between, df_within).1,
+    })
+}
+
+// Welch's one-way analysis of variance that several groups have equal means, without assuming
+// equal variances. Each group needs at least two values and some variance. The denominator degrees
+// of freedom are usually fractional.
+// R: oneway.test(value ~ group)
+pub fn welch_anova(groups: &[Stats]) -> Result<FTestResult> {
+    if groups.len() < 2 || groups.iter().any(|g| g.n_int <= 1) {
+        return Err(StatsError::NotEnoughData);
+    }
+    for g in groups {
+        g.require(Moments::Variance)?;
+    }
+    if groups.iter().any(|g| g.m2 == 0.0) {
+        return Err(StatsError::Undefined);
+    }
+    let k = groups.len() as f64;
+
+    // Weight each group by the inverse of the variance of its mean.
+    let weight = |g: &Stats| g.n * (g.n - 1.0) / g.m2;
+    let total_weight: f64 = groups.iter().map(weight).sum();
+    let weighted_mean = groups.iter().map(|g| weight(g) * g.mean).sum::<f64>() / total_weight;
+    let between = groups
+        .iter()
+        .map(|g| weight(g) * (g.mean - weighted_mean) * (g.mean - weighted_mean))
+        .sum::<f64>()
+        / (k - 1.0);
+    let correction: f64 = groups
+        .iter()
+        .map(|g| {
+            let w = weight(g) / total_weight;
+            (1.0 - w) * (1.0 - w) / (g.n - 1.0)
+        })
+        .sum();
+
+    let statistic = between / (1.0 + 2.0 * (k - 2.0) * correction / (k * k - 1.0));
+    let df_numerator = k - 1.0;
+    let df_denominator = (k * k - 1.0) / (3.0 * correction);
+    Ok(FTestResult {
+        statistic,
+        df_numerator,
+        df_denominator,
+        p_value: dist::f_tails(statistic, df_numerator, df_denominator).1,
+    })
+}
+
+// Incremental autocovariances and autocorrelations of a series, at lags 1 to the max lag given
+// when it is created. For each lag it accumulates the sum of the products of values that far apart,
+// and keeps the first and the last max lag values, which are needed to center the products on the
+// mean of the whole series. The values are shifted by the first value to limit cancellation.
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct Autocorrelation {
+    max_lag: usize,
+    n_int: u32,
+    shift: f64,
+    sum: f64,
+    cross: Vec<f64>, // The sum of the products at each lag, from 0.
+    head: Vec<f64>,
+    recent: VecDeque<f64>,
+}
+
+#[cfg(feature = "alloc")]
+impl Autocorrelation {
+    pub fn new(max_lag: usize) -> Result<Self> {
+        if max_lag == 0 {
+            return Err(StatsError::InvalidParameter);
+        }
+        Ok(Autocorrelation {
+            max_lag,
+            n_int: 0,
+            shift: 0.0,
+            sum: 0.0,
+            cross: vec![0.0; max_lag + 1],
+            head: Vec::with_capacity(max_lag),
+            recent: VecDeque::with_capacity(max_lag + 1),
+        })
+    }
+
+    // Update the sums with the next value of the series.
+    pub fn update(&mut self, x: f64) -> Result<()> {
+        if f64::is_nan(x) || f64::is_infinite(x) {
+            return Err(StatsError::InvalidData);
+        }
+        if self.n_int == 0 {
+            self.shift = x;
+        }
+        let y = x - self.shift;
+        self.n_int += 1;
+        self.sum += y;
+        self.cross[0] += y * y;
+        for (lag, prior) in self.recent.iter().rev().enumerate() {
+            self.cross[lag + 1] += y * prior;
+        }
+        self.recent.push_back(y);
+        if self.recent.len() > self.max_lag {
+            self.recent.pop_front();
+        }
+        if self.head.len() < self.max_lag {
+            self.head.push(y);
+        }
+        Ok(())
+    }
+
+    pub fn array_update(&mut self, data: &[f64]) -> Result<()> {
+        for v in data {
+            self.update(*v)?;
+        }
+        Ok(())
+    }
+
+    pub fn count(&self) -> u32 {
+        self.n_int
+    }
+
+    pub fn max_lag(&self) -> usize {
+        self.max_lag
+    }
+
+    // The autocovariance at the given lag, divided by the length of the series. Lag 0 is the
+    // population variance.
+    // R: acf(a, type = "covariance", plot = FALSE)$acf[lag + 1]
+    pub fn autocovariance(&self, lag: usize) -> Result<f64> {
+        if lag > self.max_lag {
+            return Err(StatsError::InvalidParameter);
+        }
+        if lag >= self.n_int as usize {
+            return Err(StatsError::NotEnoughData);
+        }
+        let n = self.n_int as f64;
+        let mean = self.sum / n;
+        // The sums of all but the last lag values, and of all but the first lag values.
+        let leading = self.sum - self.recent.iter().rev().take(lag).sum::<f64>();
+        let trailing = self.sum - self.head.iter().take(lag).sum::<f64>();
+        Ok((self.cross[lag] - mean * (leading + trailing) + (n - lag as f64) * mean * mean) / n)
+    }
+
+    // R: acf(a, plot = FALSE)$acf[lag + 1]
+    pub fn autocorrelation(&self, lag: usize) -> Result<f64> {
+        let autocovariance = self.autocovariance(lag)?;
+        let variance = self.autocovariance(0)?;
+        if variance == 0.0 {
+            return Err(StatsError::Undefined);
+        }
+        Ok(autocovariance / variance)
+    }
+
+    // Ljung-Box test that the series has no serial correlation up to the given number of lags,
+    // which can't be more than the max lag.
+    // R: Box.test(a, lag = lags, type = "Ljung-Box")
+    pub fn ljung_box(&self, lags: usize) -> Result<TestResult> {
+        if lags == 0 {
+            return Err(StatsError::InvalidParameter);
+        }
+        let autocorrelations = (1..=lags)
+            .map(|lag| self.autocorrelation(lag))
+            .collect::<Result<Vec<f64>>>()?;
+        Ok(hypothesis::ljung_box(self.n_int as f64, &autocorrelations))
+    }
 }
@@ -0,0 +1,149 @@
+use crate::batch;
+use crate::error::{Result, StatsError};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::float::Float;
+use alloc::vec::Vec;
+
+//
+// Kernel density estimation
+//
+// A kernel density estimate smooths the data into a continuous density by placing a kernel on each
+// value and averaging them. As in R's density(), the kernels are scaled so that the bandwidth is
+// their standard deviation, so the same bandwidth gives a similar amount of smoothing with either
+// kernel. The densities are summed directly over the data rather than approximated on a grid.
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Kernel {
+    Gaussian,
+    // Parabolic kernel with finite support, sqrt(5) bandwidths on each side.
+    Epanechnikov,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Bandwidth {
+    // Silverman's rule of thumb.
+    Silverman,
+    // Scott's rule.
+    Scott,
+    // The given bandwidth, which must be positive.
+    Fixed(f64),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Kde<'a> {
+    data: &'a [f64],
+    kernel: Kernel,
+    bandwidth: f64,
+}
+
+impl<'a> Kde<'a> {
+    pub fn new(data: &'a [f64], kernel: Kernel, bandwidth: Bandwidth) -> Result<Self> {
+        batch::validate(data)?;
+        let bandwidth = match bandwidth {
+            Bandwidth::Silverman => silverman_bandwidth(data)?,
+            Bandwidth::Scott => scott_bandwidth(data)?,
+            Bandwidth::Fixed(bandwidth) => {
+                if data.is_empty() {
+                    return Err(StatsError::NotEnoughData);
+                }
+                if !(f64::is_finite(bandwidth) && bandwidth > 0.0) {
+                    return Err(StatsError::InvalidParameter);
+                }
+                bandwidth
+            }
+        };
+        Ok(Kde {
+            data,
+            kernel,
+            bandwidth,
+        })
+    }
+
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    // The estimated density at x.
+    // R: approx(density(a, bw, kernel = "epanechnikov"), xout = x)$y
+    pub fn density(&self, x: f64) -> Result<f64> {
+        if f64::is_nan(x) || f64::is_infinite(x) {
+            return Err(StatsError::InvalidData);
+        }
+        let h = self.bandwidth;
+        let sum: f64 = match self.kernel {
+            Kernel::Gaussian => {
+                self.data
+                    .iter()
+                    .map(|v| {
+                        let u = (x - v) / h;
+                        f64::exp(-0.5 * u * u)
+                    })
+                    .sum::<f64>()
+                    * FRAC_1_SQRT_2PI
+            }
+            Kernel::Epanechnikov => {
+                let a = h * f64::sqrt(5.0);
+                self.data
+                    .iter()
+                    .map(|v| {
+                        let u = (x - v) / a;
+                        f64::max(0.0, 1.0 - u * u)
+                    })
+                    .sum::<f64>()
+                    * 0.75
+                    / f64::sqrt(5.0)
+            }
+        };
+        Ok(sum / (self.data.len() as f64 * h))
+    }
+
+    // The estimated densities at each of the points.
+    pub fn densities(&self, points: &[f64]) -> Result<Vec<f64>> {
+        points.iter().map(|x| self.density(*x)).collect()
+    }
+
+    // The estimated densities at evenly spaced points from 3 bandwidths below the smallest value to
+    // 3 bandwidths above the largest. Returns the points and the densities.
+    // R: density(a, bw, n = points, cut = 3)
+    pub fn grid(&self, points: usize) -> Result<(Vec<f64>, Vec<f64>)> {
+        if points < 2 {
+            return Err(StatsError::InvalidParameter);
+        }
+        let from = batch::min(self.data)? - 3.0 * self.bandwidth;
+        let to = batch::max(self.data)? + 3.0 * self.bandwidth;
+        let step = (to - from) / (points - 1) as f64;
+        let mut xs: Vec<f64> = (0..points - 1).map(|i| from + step * i as f64).collect();
+        xs.push(to);
+        let densities = self.densities(&xs)?;
+        Ok((xs, densities))
+    }
+}
+
+const FRAC_1_SQRT_2PI: f64 = 0.3989422804014327;
+
+// The smaller of the sample standard deviation and the IQR scaled to match it for normal data. If
+// that is zero, the standard deviation.
+fn spread(data: &[f64]) -> Result<f64> {
+    let sd = batch::sample_standard_deviation(data)?;
+    let spread = f64::min(sd, batch::interquartile_range(data)? / 1.34);
+    if spread > 0.0 {
+        Ok(spread)
+    } else if sd > 0.0 {
+        Ok(sd)
+    } else {
+        Err(StatsError::Undefined)
+    }
+}
+
+// Silverman's rule of thumb, 0.9 min(sd, IQR / 1.34) n^(-1/5).
+// R: bw.nrd0(a)
+pub fn silverman_bandwidth(data: &[f64]) -> Result<f64> {
+    Ok(0.9 * spread(data)? * f64::powf(data.len() as f64, -0.2))
+}
+
+// Scott's rule, 1.06 min(sd, IQR / 1.34) n^(-1/5).
+// R: bw.nrd(a)
+pub fn scott_bandwidth(data: &[f64]) -> Result<f64> {
+    Ok(1.06 * spread(data)? * f64::powf(data.len() as f64, -0.2))
+}
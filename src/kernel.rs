@@ -0,0 +1,159 @@
+//
+// Power sum kernels
+//
+// vec::descriptive(), incr::Stats::update_many(), and the parallel chunks need the sum, min, max,
+// and the sums of the second, third, and fourth powers of the deviations from the mean. These
+// kernels calculate them in two passes over the data: one for the sum, min, and max, which give the
+// mean, and one for all of the power sums. Each pass accumulates eight lanes at a time, so the
+// order of the additions, and so the rounding, differs slightly from the batch functions. With the
+// simd feature, the lanes are explicit SIMD vectors rather than left to the compiler to vectorize.
+
+#[cfg(feature = "simd")]
+use wide::f64x4;
+
+// The number of independent accumulators, which lets the additions of each pass be pipelined or
+// vectorized.
+const LANES: usize = 8;
+
+// The sum, min, and max of non-empty data. The min and max are compared rather than taken with
+// f64::min() and f64::max(), whose handling of NaN keeps them from being vectorized. The data has
+// been validated, or its NaNs show up in the sum.
+#[cfg(not(feature = "simd"))]
+pub(crate) fn sum_min_max(data: &[f64]) -> (f64, f64, f64) {
+    let chunks = data.chunks_exact(LANES);
+    let remainder = chunks.remainder();
+    let mut sum = [0.0; LANES];
+    let mut min = [f64::INFINITY; LANES];
+    let mut max = [f64::NEG_INFINITY; LANES];
+    for chunk in chunks {
+        for i in 0..LANES {
+            sum[i] += chunk[i];
+            min[i] = if chunk[i] < min[i] { chunk[i] } else { min[i] };
+            max[i] = if chunk[i] > max[i] { chunk[i] } else { max[i] };
+        }
+    }
+    let min = min.into_iter().fold(f64::INFINITY, f64::min);
+    let max = max.into_iter().fold(f64::NEG_INFINITY, f64::max);
+    remainder
+        .iter()
+        .fold((reduce_add(sum), min, max), |(sum, min, max), &v| {
+            (sum + v, min.min(v), max.max(v))
+        })
+}
+
+// The sums of the squares, cubes, and fourth powers of the deviations from the mean.
+#[cfg(not(feature = "simd"))]
+pub(crate) fn central_power_sums(data: &[f64], mean: f64) -> (f64, f64, f64) {
+    let chunks = data.chunks_exact(LANES);
+    let remainder = chunks.remainder();
+    let (mut m2, mut m3, mut m4) = ([0.0; LANES], [0.0; LANES], [0.0; LANES]);
+    for chunk in chunks {
+        for i in 0..LANES {
+            let delta = chunk[i] - mean;
+            let delta2 = delta * delta;
+            m2[i] += delta2;
+            m3[i] += delta2 * delta;
+            m4[i] += delta2 * delta2;
+        }
+    }
+    remainder.iter().fold(
+        (reduce_add(m2), reduce_add(m3), reduce_add(m4)),
+        power_sums(mean),
+    )
+}
+
+// Add the lanes pairwise.
+#[cfg(not(feature = "simd"))]
+fn reduce_add(mut lanes: [f64; LANES]) -> f64 {
+    let mut width = LANES / 2;
+    while width > 0 {
+        for i in 0..width {
+            lanes[i] += lanes[i + width];
+        }
+        width /= 2;
+    }
+    lanes[0]
+}
+
+// The lanes are two vectors of four, so that the additions of each can be pipelined.
+#[cfg(feature = "simd")]
+fn vectors(chunk: &[f64]) -> (f64x4, f64x4) {
+    (
+        f64x4::new([chunk[0], chunk[1], chunk[2], chunk[3]]),
+        f64x4::new([chunk[4], chunk[5], chunk[6], chunk[7]]),
+    )
+}
+
+// As above, the min and max don't handle NaN, which lets them be single instructions.
+#[cfg(feature = "simd")]
+pub(crate) fn sum_min_max(data: &[f64]) -> (f64, f64, f64) {
+    let chunks = data.chunks_exact(LANES);
+    let remainder = chunks.remainder();
+    let (mut sum_a, mut sum_b) = (f64x4::splat(0.0), f64x4::splat(0.0));
+    let (mut min_a, mut min_b) = (f64x4::splat(f64::INFINITY), f64x4::splat(f64::INFINITY));
+    let (mut max_a, mut max_b) = (
+        f64x4::splat(f64::NEG_INFINITY),
+        f64x4::splat(f64::NEG_INFINITY),
+    );
+    for chunk in chunks {
+        let (a, b) = vectors(chunk);
+        sum_a += a;
+        sum_b += b;
+        min_a = min_a.fast_min(a);
+        min_b = min_b.fast_min(b);
+        max_a = max_a.fast_max(a);
+        max_b = max_b.fast_max(b);
+    }
+    let min = min_a
+        .min(min_b)
+        .to_array()
+        .into_iter()
+        .fold(f64::INFINITY, f64::min);
+    let max = max_a
+        .max(max_b)
+        .to_array()
+        .into_iter()
+        .fold(f64::NEG_INFINITY, f64::max);
+    remainder.iter().fold(
+        ((sum_a + sum_b).reduce_add(), min, max),
+        |(sum, min, max), &v| (sum + v, min.min(v), max.max(v)),
+    )
+}
+
+#[cfg(feature = "simd")]
+pub(crate) fn central_power_sums(data: &[f64], mean: f64) -> (f64, f64, f64) {
+    let chunks = data.chunks_exact(LANES);
+    let remainder = chunks.remainder();
+    let mean4 = f64x4::splat(mean);
+    let zero = f64x4::splat(0.0);
+    let (mut m2_a, mut m3_a, mut m4_a) = (zero, zero, zero);
+    let (mut m2_b, mut m3_b, mut m4_b) = (zero, zero, zero);
+    for chunk in chunks {
+        let (a, b) = vectors(chunk);
+        let (delta_a, delta_b) = (a - mean4, b - mean4);
+        let (delta2_a, delta2_b) = (delta_a * delta_a, delta_b * delta_b);
+        m2_a += delta2_a;
+        m2_b += delta2_b;
+        m3_a += delta2_a * delta_a;
+        m3_b += delta2_b * delta_b;
+        m4_a += delta2_a * delta2_a;
+        m4_b += delta2_b * delta2_b;
+    }
+    remainder.iter().fold(
+        (
+            (m2_a + m2_b).reduce_add(),
+            (m3_a + m3_b).reduce_add(),
+            (m4_a + m4_b).reduce_add(),
+        ),
+        power_sums(mean),
+    )
+}
+
+// Add a value's powers of its deviation from the mean to the power sums.
+fn power_sums(mean: f64) -> impl Fn((f64, f64, f64), &f64) -> (f64, f64, f64) {
+    move |(m2, m3, m4), v| {
+        let delta = v - mean;
+        let delta2 = delta * delta;
+        (m2 + delta2, m3 + delta2 * delta, m4 + delta2 * delta2)
+    }
+}
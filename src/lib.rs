@@ -1,14 +1,86 @@
+// Without the std feature, the crate needs core and libm for the floating point functions, and the
+// alloc feature for the Vecs of the batch and vec functions and histograms.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("incr_stats needs either the std or the libm feature");
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+// The tests use std, even when the crate doesn't.
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
+
 pub mod batch;
+pub mod change;
+pub mod dist;
+#[cfg(feature = "alloc")]
+pub mod ecdf;
 pub mod error;
+#[cfg(not(feature = "std"))]
+mod float;
+#[cfg(feature = "alloc")]
+pub mod hist;
+pub mod hypothesis;
 pub mod incr;
+#[cfg(feature = "alloc")]
+pub mod kde;
+mod kernel;
+#[cfg(feature = "alloc")]
+pub mod outlier;
+#[cfg(feature = "rayon")]
+mod par;
+pub mod scale;
+#[cfg(feature = "std")]
+pub mod shared;
+#[cfg(feature = "futures")]
+pub mod stream;
+pub mod timed;
+#[cfg(feature = "alloc")]
 pub mod vec;
+pub mod window;
 
 #[cfg(test)]
+// The original tests predate the lint gate, and are kept as they were written.
+#[allow(
+    clippy::approx_constant,
+    clippy::crate_in_macro_def,
+    clippy::excessive_precision,
+    clippy::needless_borrow,
+    clippy::unnecessary_mut_passed,
+    clippy::useless_vec,
+    unused_mut
+)]
 mod tests {
     mod array_test;
     mod batch_test;
+    mod change_test;
     pub mod check;
+    mod dist_test;
+    #[cfg(feature = "alloc")]
+    mod ecdf_test;
+    #[cfg(feature = "alloc")]
     mod equivalence_test;
+    #[cfg(feature = "alloc")]
+    mod hist_test;
+    #[cfg(feature = "alloc")]
+    mod hypothesis_test;
     mod incr_test;
+    #[cfg(feature = "alloc")]
+    mod kde_test;
+    #[cfg(feature = "alloc")]
+    mod outlier_test;
+    #[cfg(feature = "rayon")]
+    mod par_test;
+    #[cfg(feature = "alloc")]
+    mod scale_test;
+    #[cfg(feature = "std")]
+    mod shared_test;
+    #[cfg(feature = "futures")]
+    mod stream_test;
+    mod timed_test;
+    #[cfg(feature = "alloc")]
     mod vec_test;
+    mod window_test;
 }
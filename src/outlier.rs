@@ -0,0 +1,250 @@
+use crate::error::{Result, StatsError};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::float::Float;
+use crate::hist::Histogram;
+use crate::incr::{Moments, Stats};
+
+//
+// Online outlier detection
+//
+// A Detector owns an incr Stats and classifies each value as it arrives, by how far it is from the
+// data seen so far. The value can be judged before it updates the stats, or after, in which case
+// it contributes to its own judgement. Outliers can be excluded so that they don't distort the
+// stats used to judge later values.
+//
+// The z-score only needs the running moments. The modified z-score and Tukey's fences need the
+// median and quartiles, which are approximated from the histogram of a Stats created with
+// Stats::with_histogram().
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Method {
+    // The number of sample standard deviations from the mean. 3 is a common threshold.
+    ZScore(f64),
+    // Iglewicz and Hoaglin's modified z-score, 0.6745 (x - median) / MAD, where MAD is the median
+    // absolute deviation. 3.5 is the usual threshold.
+    ModifiedZScore(f64),
+    // Tukey's fences, the distance below the first quartile or above the third quartile in IQRs.
+    // 1.5 is the usual threshold, and 3 flags only far outliers.
+    Tukey(f64),
+}
+
+impl Method {
+    fn threshold(&self) -> f64 {
+        match *self {
+            Method::ZScore(t) | Method::ModifiedZScore(t) | Method::Tukey(t) => t,
+        }
+    }
+}
+
+// Whether a value is judged against the stats before or after it updates them.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Timing {
+    Before,
+    After,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Verdict {
+    Inlier,
+    Outlier,
+    // There isn't enough data, or spread in the data, to judge the value yet. It always updates
+    // the stats.
+    Undetermined,
+}
+
+pub struct Detector {
+    stats: Stats,
+    method: Method,
+    timing: Timing,
+    exclude_outliers: bool,
+    outliers: u32,
+}
+
+impl Detector {
+    // The threshold of the method must be positive, the stats must maintain the variance for the
+    // z-score, and they must have a histogram for the modified z-score and Tukey's fences.
+    pub fn new(
+        stats: Stats,
+        method: Method,
+        timing: Timing,
+        exclude_outliers: bool,
+    ) -> Result<Self> {
+        let threshold = method.threshold();
+        if !(f64::is_finite(threshold) && threshold > 0.0) {
+            return Err(StatsError::InvalidParameter);
+        }
+        let usable = match method {
+            Method::ZScore(_) => stats.moments() >= Moments::Variance,
+            _ => stats.histogram().is_some(),
+        };
+        if !usable {
+            return Err(StatsError::InvalidParameter);
+        }
+        Ok(Detector {
+            stats,
+            method,
+            timing,
+            exclude_outliers,
+            outliers: 0,
+        })
+    }
+
+    // Classify the value, and update the stats with it unless it is an excluded outlier. When
+    // outliers are excluded and judged after the update, the value is scored as if it had been
+    // added, and then only added if it isn't an outlier.
+    pub fn update(&mut self, x: f64) -> Result<Verdict> {
+        if f64::is_nan(x) || f64::is_infinite(x) {
+            return Err(StatsError::InvalidData);
+        }
+        let verdict = match self.timing {
+            Timing::Before => {
+                let verdict = self.classify(&self.stats, x);
+                if !(verdict == Verdict::Outlier && self.exclude_outliers) {
+                    self.stats.update(x)?;
+                }
+                verdict
+            }
+            Timing::After if self.exclude_outliers => {
+                let score = score_including(&mut self.stats, self.method, x);
+                let verdict = self.verdict(score);
+                if verdict != Verdict::Outlier {
+                    self.stats.update(x)?;
+                }
+                verdict
+            }
+            Timing::After => {
+                self.stats.update(x)?;
+                self.classify(&self.stats, x)
+            }
+        };
+        if verdict == Verdict::Outlier {
+            self.outliers += 1;
+        }
+        Ok(verdict)
+    }
+
+    // The score of the value against the current stats, without updating them: the z-score,
+    // modified z-score, or distance outside the quartiles in IQRs, which is zero between them.
+    pub fn score(&self, x: f64) -> Result<f64> {
+        if f64::is_nan(x) || f64::is_infinite(x) {
+            return Err(StatsError::InvalidData);
+        }
+        score(&self.stats, self.method, x)
+    }
+
+    // The number of values classified as outliers.
+    pub fn outliers(&self) -> u32 {
+        self.outliers
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    pub fn into_stats(self) -> Stats {
+        self.stats
+    }
+
+    fn classify(&self, stats: &Stats, x: f64) -> Verdict {
+        self.verdict(score(stats, self.method, x))
+    }
+
+    fn verdict(&self, score: Result<f64>) -> Verdict {
+        match score {
+            Ok(score) if f64::abs(score) > self.method.threshold() => Verdict::Outlier,
+            Ok(_) => Verdict::Inlier,
+            Err(_) => Verdict::Undetermined,
+        }
+    }
+}
+
+fn score(stats: &Stats, method: Method, x: f64) -> Result<f64> {
+    match method {
+        Method::ZScore(_) => {
+            let sd = stats.sample_standard_deviation()?;
+            if sd == 0.0 {
+                return Err(StatsError::Undefined);
+            }
+            Ok((x - stats.mean()?) / sd)
+        }
+        Method::ModifiedZScore(_) => {
+            let histogram = stats.histogram().ok_or(StatsError::InvalidParameter)?;
+            let median = histogram.quantile(0.5)?;
+            let mad = median_absolute_deviation(histogram, median)?;
+            if mad == 0.0 {
+                return Err(StatsError::Undefined);
+            }
+            Ok(0.6745 * (x - median) / mad)
+        }
+        Method::Tukey(_) => {
+            let histogram = stats.histogram().ok_or(StatsError::InvalidParameter)?;
+            let q1 = histogram.quantile(0.25)?;
+            let q3 = histogram.quantile(0.75)?;
+            if q3 == q1 {
+                return Err(StatsError::Undefined);
+            }
+            if x < q1 {
+                Ok((x - q1) / (q3 - q1))
+            } else if x > q3 {
+                Ok((x - q3) / (q3 - q1))
+            } else {
+                Ok(0.0)
+            }
+        }
+    }
+}
+
+// The score of the value against the stats as if it had been added to them, leaving them as they
+// were. The z-score uses the mean and sample standard deviation that Welford's update would give.
+// The histogram methods count the value in the histogram while it is scored.
+fn score_including(stats: &mut Stats, method: Method, x: f64) -> Result<f64> {
+    match method {
+        Method::ZScore(_) => {
+            let n = stats.count() as f64 + 1.0;
+            if n < 2.0 {
+                return Err(StatsError::NotEnoughData);
+            }
+            let delta = x - stats.mean()?;
+            let m2 = if n > 2.0 {
+                stats.sample_variance()? * (n - 2.0)
+            } else {
+                0.0
+            };
+            let sd = f64::sqrt((m2 + delta * delta * (n - 1.0) / n) / (n - 1.0));
+            if sd == 0.0 {
+                return Err(StatsError::Undefined);
+            }
+            Ok((delta - delta / n) / sd)
+        }
+        Method::ModifiedZScore(_) | Method::Tukey(_) => {
+            let histogram = stats.histogram_mut().ok_or(StatsError::InvalidParameter)?;
+            let (min, max) = (histogram.min().unwrap_or(x), histogram.max().unwrap_or(x));
+            histogram.record(x);
+            let score = score(stats, method, x);
+            if let Some(histogram) = stats.histogram_mut() {
+                histogram.unrecord(x, min, max);
+            }
+            score
+        }
+    }
+}
+
+// The median absolute deviation from the histogram, the distance from the median within which half
+// of the values lie. The histogram's CDF is continuous and increasing, so bisection finds it.
+fn median_absolute_deviation(histogram: &Histogram, median: f64) -> Result<f64> {
+    let mut lo = 0.0;
+    let mut hi = f64::max(histogram.max()? - median, median - histogram.min()?);
+    for _ in 0..100 {
+        let mid = 0.5 * (lo + hi);
+        if mid == lo || mid == hi {
+            break;
+        }
+        if histogram.cdf(median + mid)? - histogram.cdf(median - mid)? < 0.5 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok(hi)
+}
@@ -0,0 +1,66 @@
+use rayon::prelude::*;
+
+use crate::incr::Stats;
+
+//
+// Parallel moments
+//
+// With the rayon feature, the batch functions and vec::descriptive() split large arrays into
+// chunks, calculate the moments of each chunk on its own thread, and merge them with
+// incr::Stats::merge(). Smaller arrays aren't worth the threading overhead, so the functions
+// calculate them serially as usual.
+
+// The smallest array that is calculated in parallel.
+const MIN_PARALLEL_LEN: usize = 1 << 16;
+
+// The length of the chunks, long enough to amortize the cost of merging them.
+const CHUNK_LEN: usize = 1 << 14;
+
+// The stats of large arrays, or None for arrays that should be calculated serially. As in the
+// batch functions, the data isn't validated, so NaNs and Infs produce NaN results.
+pub(crate) fn stats(data: &[f64]) -> Option<Stats> {
+    if data.len() < MIN_PARALLEL_LEN {
+        return None;
+    }
+    Some(
+        data.par_chunks(CHUNK_LEN)
+            .map(Stats::from_slice)
+            .reduce(Stats::new, |mut a, b| {
+                // Neither has a histogram, so merging can't fail.
+                let _ = a.merge(&b);
+                a
+            }),
+    )
+}
+
+// The min, max, and sum only need a single reduction, rather than all of the moments.
+pub(crate) fn min(data: &[f64]) -> Option<f64> {
+    reduce(data, f64::INFINITY, f64::min)
+}
+
+pub(crate) fn max(data: &[f64]) -> Option<f64> {
+    reduce(data, f64::NEG_INFINITY, f64::max)
+}
+
+pub(crate) fn sum(data: &[f64]) -> Option<f64> {
+    reduce(data, 0.0, |a, b| a + b)
+}
+
+fn reduce(data: &[f64], identity: f64, op: fn(f64, f64) -> f64) -> Option<f64> {
+    if data.len() < MIN_PARALLEL_LEN {
+        return None;
+    }
+    Some(
+        data.par_chunks(CHUNK_LEN)
+            .map(|chunk| chunk.iter().copied().fold(identity, op))
+            .reduce(|| identity, op),
+    )
+}
+
+// Check the data for NaNs and Infs in parallel, or None for small arrays.
+pub(crate) fn is_valid(data: &[f64]) -> Option<bool> {
+    if data.len() < MIN_PARALLEL_LEN {
+        return None;
+    }
+    Some(data.par_iter().all(|v| f64::is_finite(*v)))
+}
@@ -0,0 +1,70 @@
+use crate::error::{Result, StatsError};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+//
+// Scaling
+//
+// A Scaler holds the parameters of a linear transform, x' = (x - center) / scale, so that they
+// can be fitted once from the stats of a training set and then applied to any data. The incr and
+// vec Stats create scalers for the common standardizations:
+//   z-score: the mean and sample standard deviation, as in R's scale()
+//   min-max: the min and the range, which maps the data onto [0, 1]
+//   robust: the median and the interquartile range, which are insensitive to outliers
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Scaler {
+    center: f64,
+    scale: f64,
+}
+
+impl Scaler {
+    // A scaler with the given parameters, for example ones saved from an earlier fit. The scale
+    // must be nonzero.
+    pub fn new(center: f64, scale: f64) -> Result<Self> {
+        if !f64::is_finite(center) || !f64::is_finite(scale) || scale == 0.0 {
+            return Err(StatsError::InvalidParameter);
+        }
+        Ok(Scaler { center, scale })
+    }
+
+    // A scaler fitted from the stats, which can't scale data without any spread.
+    pub(crate) fn fit(center: f64, scale: f64) -> Result<Self> {
+        if scale == 0.0 {
+            return Err(StatsError::Undefined);
+        }
+        Ok(Scaler { center, scale })
+    }
+
+    pub fn center(&self) -> f64 {
+        self.center
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    // R: scale(a, center, scale)
+    #[cfg(feature = "alloc")]
+    pub fn transform(&self, data: &[f64]) -> Vec<f64> {
+        data.iter()
+            .map(|x| (x - self.center) / self.scale)
+            .collect()
+    }
+
+    pub fn transform_in_place(&self, data: &mut [f64]) {
+        data.iter_mut()
+            .for_each(|x| *x = (*x - self.center) / self.scale);
+    }
+
+    // Undo the transform, returning the data to its original units.
+    #[cfg(feature = "alloc")]
+    pub fn inverse_transform(&self, data: &[f64]) -> Vec<f64> {
+        data.iter().map(|x| x * self.scale + self.center).collect()
+    }
+
+    pub fn inverse_transform_in_place(&self, data: &mut [f64]) {
+        data.iter_mut()
+            .for_each(|x| *x = *x * self.scale + self.center);
+    }
+}
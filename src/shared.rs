@@ -0,0 +1,100 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread;
+
+use crate::error::{Result, StatsError};
+use crate::incr::Stats;
+
+//
+// Shared stats
+//
+// SharedStats can be updated by many threads through a shared reference, such as from an Arc or a
+// static. Rather than one Stats behind one lock, which the threads would contend for, it keeps
+// several shards, each an incr Stats behind its own lock. Each thread updates the shard it was
+// assigned, so threads rarely wait on each other, and the shards are merged when the stats are
+// read.
+
+// The shard that each thread updates, assigned round-robin as threads first update any SharedStats.
+static NEXT_SHARD: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static SHARD: usize = NEXT_SHARD.fetch_add(1, Ordering::Relaxed);
+}
+
+// A shard, aligned to keep the shards' locks on separate cache lines.
+#[repr(align(128))]
+struct Shard(Mutex<Stats>);
+
+pub struct SharedStats {
+    shards: Vec<Shard>,
+}
+
+impl SharedStats {
+    // Create stats with a shard for each of the CPUs.
+    pub fn new() -> Self {
+        let shards = thread::available_parallelism().map_or(1, |n| n.get());
+        SharedStats {
+            shards: (0..shards)
+                .map(|_| Shard(Mutex::new(Stats::new())))
+                .collect(),
+        }
+    }
+
+    // Create stats with the given number of shards, each a copy of the given empty stats, such as
+    // one created with Stats::with_histogram() or Stats::with_moments().
+    pub fn with_shards(stats: Stats, shards: usize) -> Result<Self> {
+        if shards == 0 || stats.count() != 0 {
+            return Err(StatsError::InvalidParameter);
+        }
+        Ok(SharedStats {
+            shards: (0..shards)
+                .map(|_| Shard(Mutex::new(stats.clone())))
+                .collect(),
+        })
+    }
+
+    // Update the calling thread's shard with the given value.
+    pub fn update(&self, x: f64) -> Result<()> {
+        self.shard().update(x)
+    }
+
+    // Update the calling thread's shard with all of the values, or none of them if any is a NaN or
+    // Inf.
+    pub fn update_many(&self, data: &[f64]) -> Result<()> {
+        self.shard().update_many(data)
+    }
+
+    // The stats of all of the values so far. All of the shards are locked before they're merged,
+    // so the snapshot includes each update either entirely or not at all, even from a thread's
+    // update_many().
+    pub fn snapshot(&self) -> Stats {
+        let shards: Vec<_> = self.shards.iter().map(|shard| lock(&shard.0)).collect();
+        let mut stats = shards[0].clone();
+        for shard in &shards[1..] {
+            // The shards are copies of the same stats, so their histograms always merge.
+            let _ = stats.merge(shard);
+        }
+        stats
+    }
+
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    fn shard(&self) -> MutexGuard<'_, Stats> {
+        let i = SHARD.with(|shard| *shard) % self.shards.len();
+        lock(&self.shards[i].0)
+    }
+}
+
+impl Default for SharedStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Lock a shard. The stats are never left partly updated, so a shard is still usable after a panic
+// in another thread poisoned its lock.
+fn lock(shard: &Mutex<Stats>) -> MutexGuard<'_, Stats> {
+    shard.lock().unwrap_or_else(PoisonError::into_inner)
+}
@@ -0,0 +1,214 @@
+use core::mem;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use futures_sink::Sink;
+
+use crate::error::{Result, StatsError};
+use crate::incr::Stats;
+
+//
+// Async streams
+//
+// Adapters between futures Streams of values and incr Stats. The summaries adapters consume a
+// stream of values and emit the Stats of the values since the previous summary, either every N
+// values or whenever a stream of ticks, such as a runtime's interval timer, yields. The StatsSink
+// collects the values sent to it, such as by StreamExt::forward(), for the caller to take.
+//
+// Each summary starts as a copy of the given empty stats, so it can have a histogram or limited
+// moments. The streams must be Unpin, which a boxed stream from Box::pin() is.
+
+// The most values taken in one poll, so that a stream whose values are always ready still yields
+// to the executor, and to the other tasks on its thread.
+const BUDGET: u32 = 1024;
+
+pub trait SummariesExt: Stream<Item = f64> + Sized {
+    // Emit the stats of every `every` values, then of any left when the stream ends.
+    fn summaries(self, stats: Stats, every: u32) -> Result<Summaries<Self>> {
+        if every == 0 {
+            return Err(StatsError::InvalidParameter);
+        }
+        Ok(Summaries {
+            values: Some(self),
+            every,
+            window: Window::new(stats)?,
+        })
+    }
+
+    // Emit the stats of the values since the last tick each time the ticks stream yields, then of
+    // any left when the stream ends. An interval without values has empty stats. The ticks are
+    // checked after each value.
+    fn summaries_on<T: Stream>(self, stats: Stats, ticks: T) -> Result<TickedSummaries<Self, T>> {
+        Ok(TickedSummaries {
+            values: Some(self),
+            ticks: Some(ticks),
+            window: Window::new(stats)?,
+        })
+    }
+}
+
+impl<S: Stream<Item = f64>> SummariesExt for S {}
+
+// The stats of the values since the last summary.
+struct Window {
+    empty: Stats,
+    stats: Stats,
+}
+
+impl Window {
+    fn new(stats: Stats) -> Result<Self> {
+        if stats.count() != 0 {
+            return Err(StatsError::InvalidParameter);
+        }
+        Ok(Window {
+            empty: stats.clone(),
+            stats,
+        })
+    }
+
+    fn take(&mut self) -> Stats {
+        mem::replace(&mut self.stats, self.empty.clone())
+    }
+
+    // The last summary, when the values have ended.
+    fn finish(&mut self) -> Option<Result<Stats>> {
+        (self.stats.count() != 0).then(|| Ok(self.take()))
+    }
+}
+
+// A NaN or Inf value is emitted as an InvalidData error, and the stream continues without it.
+pub struct Summaries<S> {
+    values: Option<S>,
+    every: u32,
+    window: Window,
+}
+
+impl<S: Stream<Item = f64> + Unpin> Stream for Summaries<S> {
+    type Item = Result<Stats>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let mut budget = BUDGET;
+        while let Some(values) = &mut this.values {
+            match ready!(Pin::new(values).poll_next(cx)) {
+                Some(x) => {
+                    if let Err(e) = this.window.stats.update(x) {
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    if this.window.stats.count() == this.every {
+                        return Poll::Ready(Some(Ok(this.window.take())));
+                    }
+                }
+                None => this.values = None,
+            }
+            budget -= 1;
+            if budget == 0 {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+        }
+        Poll::Ready(this.window.finish())
+    }
+}
+
+// A NaN or Inf value is emitted as an InvalidData error, and the stream continues without it. If
+// the ticks end first, the values are summarized once they end.
+pub struct TickedSummaries<S, T> {
+    values: Option<S>,
+    ticks: Option<T>,
+    window: Window,
+}
+
+impl<S, T> Stream for TickedSummaries<S, T>
+where
+    S: Stream<Item = f64> + Unpin,
+    T: Stream + Unpin,
+{
+    type Item = Result<Stats>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let mut budget = BUDGET;
+        while let Some(values) = &mut this.values {
+            let value = Pin::new(values).poll_next(cx);
+            match value {
+                Poll::Ready(Some(x)) => {
+                    if let Err(e) = this.window.stats.update(x) {
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                }
+                Poll::Ready(None) => {
+                    this.values = None;
+                    break;
+                }
+                Poll::Pending => {}
+            }
+            // The ticks are checked after every value, so a busy stream doesn't delay them.
+            if let Some(ticks) = &mut this.ticks {
+                match Pin::new(ticks).poll_next(cx) {
+                    Poll::Ready(Some(_)) => return Poll::Ready(Some(Ok(this.window.take()))),
+                    Poll::Ready(None) => this.ticks = None,
+                    Poll::Pending => {}
+                }
+            }
+            if value.is_pending() {
+                return Poll::Pending;
+            }
+            budget -= 1;
+            if budget == 0 {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+        }
+        Poll::Ready(this.window.finish())
+    }
+}
+
+// A Sink that updates stats with the values sent to it. A NaN or Inf value is rejected with an
+// InvalidData error.
+pub struct StatsSink {
+    window: Window,
+}
+
+impl StatsSink {
+    // Collect the values into a copy of the given empty stats.
+    pub fn new(stats: Stats) -> Result<Self> {
+        Ok(StatsSink {
+            window: Window::new(stats)?,
+        })
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.window.stats
+    }
+
+    // The stats of the values sent since the last take, starting over with empty stats.
+    pub fn take(&mut self) -> Stats {
+        self.window.take()
+    }
+
+    pub fn into_stats(self) -> Stats {
+        self.window.stats
+    }
+}
+
+impl Sink<f64> for StatsSink {
+    type Error = StatsError;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, x: f64) -> Result<()> {
+        self.get_mut().window.stats.update(x)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
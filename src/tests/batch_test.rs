@@ -1,6 +1,14 @@
 use crate::batch;
 use crate::chk;
+#[cfg(feature = "alloc")]
+use crate::error::StatsError::InvalidParameter;
 use crate::error::StatsError::{InvalidData, NotEnoughData, Undefined};
+#[cfg(feature = "alloc")]
+use crate::hist::{BinRule, Bins};
+#[cfg(feature = "alloc")]
+use crate::incr::Stats;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use std::vec::Vec;
 
 // Test the batch functions. Calculate the descriptive stats on the whole array.
 static ZEROS: [f64; 10] = [0.0; 10];
@@ -328,3 +336,486 @@ fn test_batch_stats_ten_values() {
     chk!(batch::population_kurtosis(&a), Ok(1.253240236214162));
     chk!(batch::sample_kurtosis(&a), Ok(3.179835417592894));
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_shapiro_wilk() {
+    let a: Vec<f64> = (1..=10).map(|v| v as f64).collect();
+    let r = batch::shapiro_wilk(&a).unwrap();
+    chk!(r.statistic, 0.970164611230666);
+    chk!(r.p_value, 0.892367307523925);
+    let r = batch::shapiro_wilk(&ASCENDING).unwrap();
+    chk!(r.statistic, 0.9867621554477196);
+    chk!(r.p_value, 0.9671739359680406);
+    // The p-value is exact for 3 values.
+    let r = batch::shapiro_wilk(&[1.0, 2.0, 4.0]).unwrap();
+    chk!(r.statistic, 0.9642857142857143);
+    chk!(r.p_value, 0.6368868450289698);
+    // Larger samples use a different normalizing transformation.
+    let a: Vec<f64> = (1..=20).map(|v| (v * v) as f64).collect();
+    let r = batch::shapiro_wilk(&a).unwrap();
+    chk!(r.statistic, 0.9061306284765465);
+    chk!(r.p_value, 0.05380958882820414);
+
+    assert_eq!(batch::shapiro_wilk(&ASCENDING[..2]), Err(NotEnoughData));
+    assert_eq!(batch::shapiro_wilk(&ONES), Err(Undefined));
+    let a: Vec<f64> = (0..5001).map(|v| v as f64).collect();
+    assert_eq!(batch::shapiro_wilk(&a), Err(InvalidParameter));
+    assert_eq!(batch::shapiro_wilk(&[1.0, 2.0, f64::NAN]), Err(InvalidData));
+    assert_eq!(
+        batch::shapiro_wilk(&[1.0, 2.0, 3.0, f64::INFINITY]),
+        Err(InvalidData)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_anderson_darling() {
+    let a = vec![
+        1.0, -2.0, 13.0, 47.0, 115.0, -0.03, -123.4, 23.0, -23.04, 12.3,
+    ];
+    let r = batch::anderson_darling(&a).unwrap();
+    chk!(r.statistic, 0.6747735811986524);
+    chk!(r.p_value, 0.05371951075010746);
+    let a: Vec<f64> = (1..=20).map(|v| (v * v) as f64).collect();
+    let r = batch::anderson_darling(&a).unwrap();
+    chk!(r.statistic, 0.6251512841505881);
+    chk!(r.p_value, 0.08880683219204183);
+
+    assert_eq!(batch::anderson_darling(&a[..7]), Err(NotEnoughData));
+    assert_eq!(batch::anderson_darling(&ONES), Err(Undefined));
+    let mut a = a;
+    a[3] = f64::NAN;
+    assert_eq!(batch::anderson_darling(&a), Err(InvalidData));
+}
+
+#[cfg(feature = "alloc")]
+static VALUES: [f64; 10] = [
+    1.0, -2.0, 13.0, 47.0, 115.0, -0.03, -123.4, 23.0, -23.04, 12.3,
+];
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_range() {
+    assert_eq!(batch::range(&[]), Err(NotEnoughData));
+    chk!(batch::range(&ONES), Ok(0.0));
+    chk!(batch::range(&ASCENDING), Ok(4.0));
+    chk!(batch::range(&VALUES), Ok(238.4));
+
+    assert_eq!(batch::median(&[]), Err(NotEnoughData));
+    chk!(batch::median(&[2.0]), Ok(2.0));
+    chk!(batch::median(&ASCENDING), Ok(3.0));
+    chk!(batch::median(&VALUES), Ok(6.65));
+
+    assert_eq!(batch::interquartile_range(&[]), Err(NotEnoughData));
+    chk!(batch::interquartile_range(&[1.0]), Ok(0.0));
+    chk!(batch::interquartile_range(&ONES), Ok(0.0));
+    chk!(batch::interquartile_range(&ASCENDING), Ok(2.0));
+    chk!(batch::interquartile_range(&VALUES), Ok(22.0075));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_histogram() {
+    // The expected values were calculated with numpy's histogram().
+    let (counts, edges) = batch::histogram(&VALUES, Bins::Count(4)).unwrap();
+    assert_eq!(counts, [1, 1, 7, 1]);
+    assert_eq!(edges.len(), 5);
+    chk!(edges[0], -123.4);
+    chk!(edges[1], -63.800000000000004);
+    chk!(edges[4], 115.0);
+    let (counts, edges) =
+        batch::histogram(&VALUES, Bins::Edges(&[-100.0, 0.0, 10.0, 20.0, 200.0])).unwrap();
+    assert_eq!(counts, [3, 1, 2, 3]);
+    assert_eq!(edges, [-100.0, 0.0, 10.0, 20.0, 200.0]);
+    // The last bin includes its upper edge.
+    let (counts, _) = batch::histogram(&ASCENDING, Bins::Edges(&[1.0, 3.0, 5.0])).unwrap();
+    assert_eq!(counts, [2, 3]);
+
+    let rules = [
+        (
+            BinRule::Auto,
+            vec![1, 0, 0, 0, 0, 1, 5, 1, 1, 0, 0, 1],
+            -103.53333333333333,
+        ),
+        (BinRule::Sturges, vec![1, 0, 6, 2, 1], -75.72),
+        (BinRule::Scott, vec![1, 7, 2], -43.93333333333334),
+        (
+            BinRule::FreedmanDiaconis,
+            vec![1, 0, 0, 0, 0, 1, 5, 1, 1, 0, 0, 1],
+            -103.53333333333333,
+        ),
+        (BinRule::Sqrt, vec![1, 1, 7, 1], -63.800000000000004),
+        (BinRule::Doane, vec![1, 0, 1, 6, 1, 1], -83.66666666666667),
+    ];
+    for (rule, expected_counts, second_edge) in rules {
+        let (counts, edges) = batch::histogram(&VALUES, Bins::Rule(rule)).unwrap();
+        assert_eq!(counts, expected_counts);
+        assert_eq!(edges.len(), counts.len() + 1);
+        chk!(edges[1], second_edge);
+    }
+
+    let data: Vec<f64> = (0..100)
+        .map(|i| (i * i % 37) as f64 + i as f64 / 7.0)
+        .collect();
+    let rules = [
+        (BinRule::Auto, vec![5, 14, 15, 14, 10, 14, 17, 11]),
+        (BinRule::Sturges, vec![5, 14, 15, 14, 10, 14, 17, 11]),
+        (BinRule::Scott, vec![8, 21, 19, 15, 21, 16]),
+        (BinRule::FreedmanDiaconis, vec![8, 21, 19, 15, 21, 16]),
+        (BinRule::Sqrt, vec![3, 8, 13, 14, 10, 8, 10, 16, 10, 8]),
+        (BinRule::Doane, vec![5, 14, 15, 14, 10, 14, 17, 11]),
+    ];
+    for (rule, expected_counts) in rules {
+        let (counts, _) = batch::histogram(&data, Bins::Rule(rule)).unwrap();
+        assert_eq!(counts, expected_counts);
+    }
+
+    // Constant data spans a range of 1, in a single bin for the rules.
+    let (counts, edges) = batch::histogram(&ONES, Bins::Rule(BinRule::Doane)).unwrap();
+    assert_eq!(counts, [10]);
+    assert_eq!(edges, [0.5, 1.5]);
+    let (counts, edges) = batch::histogram(&ONES, Bins::Count(2)).unwrap();
+    assert_eq!(counts, [0, 10]);
+    assert_eq!(edges, [0.5, 1.0, 1.5]);
+    let (counts, _) = batch::histogram(&ASCENDING[..2], Bins::Rule(BinRule::Doane)).unwrap();
+    assert_eq!(counts, [2]);
+
+    let (counts, _) = batch::histogram(&[], Bins::Edges(&[0.0, 1.0])).unwrap();
+    assert_eq!(counts, [0]);
+    assert_eq!(batch::histogram(&[], Bins::Count(3)), Err(NotEnoughData));
+    assert_eq!(
+        batch::histogram(&[], Bins::Rule(BinRule::Auto)),
+        Err(NotEnoughData)
+    );
+    assert_eq!(
+        batch::histogram(&VALUES, Bins::Count(0)),
+        Err(InvalidParameter)
+    );
+    assert_eq!(
+        batch::histogram(&VALUES, Bins::Edges(&[0.0])),
+        Err(InvalidParameter)
+    );
+    assert_eq!(
+        batch::histogram(&VALUES, Bins::Edges(&[0.0, 1.0, 1.0])),
+        Err(InvalidParameter)
+    );
+    assert_eq!(
+        batch::histogram(&VALUES, Bins::Edges(&[0.0, f64::NAN])),
+        Err(InvalidParameter)
+    );
+    assert_eq!(
+        batch::histogram(&[1.0, f64::NAN], Bins::Count(3)),
+        Err(InvalidData)
+    );
+    assert_eq!(
+        batch::histogram(&[1.0, f64::INFINITY], Bins::Edges(&[0.0, 2.0])),
+        Err(InvalidData)
+    );
+
+    // Finite data whose range overflows.
+    let huge = [-1e308, 0.0, 1e308];
+    assert_eq!(batch::histogram(&huge, Bins::Count(3)), Err(Undefined));
+    for rule in [
+        BinRule::Auto,
+        BinRule::Sturges,
+        BinRule::Scott,
+        BinRule::FreedmanDiaconis,
+        BinRule::Sqrt,
+        BinRule::Doane,
+    ] {
+        assert_eq!(batch::histogram(&huge, Bins::Rule(rule)), Err(Undefined));
+    }
+    let (counts, _) = batch::histogram(&huge, Bins::Edges(&[-1e308, 0.0, 1e308])).unwrap();
+    assert_eq!(counts, [1, 2]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_rank() {
+    use crate::batch::Ties;
+
+    let data = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0, 5.0, 3.0, 5.0];
+    assert_eq!(
+        batch::rank(&data, Ties::Average),
+        [4.5, 1.5, 6.0, 1.5, 8.0, 11.0, 3.0, 10.0, 8.0, 4.5, 8.0]
+    );
+    assert_eq!(
+        batch::rank(&data, Ties::Min),
+        [4.0, 1.0, 6.0, 1.0, 7.0, 11.0, 3.0, 10.0, 7.0, 4.0, 7.0]
+    );
+    assert_eq!(
+        batch::rank(&data, Ties::Max),
+        [5.0, 2.0, 6.0, 2.0, 9.0, 11.0, 3.0, 10.0, 9.0, 5.0, 9.0]
+    );
+    assert_eq!(
+        batch::rank(&data, Ties::Dense),
+        [3.0, 1.0, 4.0, 1.0, 5.0, 7.0, 2.0, 6.0, 5.0, 3.0, 5.0]
+    );
+    assert_eq!(
+        batch::rank(&data, Ties::Ordinal),
+        [4.0, 1.0, 6.0, 2.0, 7.0, 11.0, 3.0, 10.0, 8.0, 5.0, 9.0]
+    );
+
+    // Without ties, all of the methods agree.
+    for ties in [
+        Ties::Average,
+        Ties::Min,
+        Ties::Max,
+        Ties::Dense,
+        Ties::Ordinal,
+    ] {
+        assert_eq!(
+            batch::rank(&VALUES, ties),
+            [5.0, 3.0, 7.0, 9.0, 10.0, 4.0, 1.0, 8.0, 2.0, 6.0]
+        );
+        assert_eq!(batch::rank(&[], ties), [0.0; 0]);
+    }
+    assert_eq!(batch::rank(&ONES, Ties::Average), [5.5; 10]);
+    assert_eq!(batch::rank(&ONES, Ties::Dense), [1.0; 10]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_autocorrelation() {
+    let a: Vec<f64> = (1..=10).map(f64::from).collect();
+    let acf = batch::acf(&a, 3).unwrap();
+    let expected = [1.0, 0.7, 0.4121212121212121, 0.14848484848484847];
+    for (r, e) in acf.iter().zip(expected) {
+        chk!(*r, e);
+    }
+    let pacf = batch::pacf(&a, 3).unwrap();
+    let expected = [0.7, -0.15270350564468213, -0.15490666705224043];
+    for (r, e) in pacf.iter().zip(expected) {
+        chk!(*r, e);
+    }
+
+    let s: Vec<f64> = (0..30).map(|i| f64::from(i * i % 17)).collect();
+    let acf = batch::acf(&s, 5).unwrap();
+    let expected = [
+        1.0,
+        0.2978795471417966,
+        -0.10183277654046027,
+        -0.009757795100222717,
+        -0.15237100965107647,
+        0.13458147735708983,
+    ];
+    assert_eq!(acf.len(), 6);
+    for (r, e) in acf.iter().zip(expected) {
+        chk!(*r, e);
+    }
+    let pacf = batch::pacf(&s, 5).unwrap();
+    let expected = [
+        0.2978795471417966,
+        -0.20912075055363769,
+        0.10237622376172366,
+        -0.2368056978869126,
+        0.3484518019961967,
+    ];
+    assert_eq!(pacf.len(), 5);
+    for (r, e) in pacf.iter().zip(expected) {
+        chk!(*r, e);
+    }
+    assert_eq!(batch::pacf(&s, 0), Ok(vec![]));
+
+    let r = batch::ljung_box(&s, 5).unwrap();
+    chk!(r.statistic, 4.849015711609962);
+    chk!(r.p_value, 0.4345832077628657);
+    let r = batch::ljung_box(&s, 1).unwrap();
+    chk!(r.statistic, 2.9373426076270945);
+    chk!(r.p_value, 0.0865530202096964);
+
+    // Every lag needs at least one pair of values, and the autocorrelations need some variance.
+    assert_eq!(batch::acf(&[], 0), Err(NotEnoughData));
+    assert_eq!(batch::acf(&ASCENDING, 5), Err(NotEnoughData));
+    assert_eq!(batch::acf(&ONES, 2), Err(Undefined));
+    assert_eq!(batch::acf(&[1.0, f64::NAN], 1), Err(InvalidData));
+    assert_eq!(batch::ljung_box(&s, 0), Err(InvalidParameter));
+}
+
+// Check each rolling result against the batch function on the window's slice.
+#[cfg(feature = "alloc")]
+fn check_rolling(
+    data: &[f64],
+    window: usize,
+    rolling: fn(&[f64], usize, batch::Align) -> crate::error::Result<Vec<f64>>,
+    batch: fn(&[f64]) -> crate::error::Result<f64>,
+) {
+    let r = rolling(data, window, batch::Align::Right).unwrap();
+    assert_eq!(r.len(), data.len());
+    for (i, v) in r.iter().enumerate() {
+        match (i + 1)
+            .checked_sub(window)
+            .map(|start| batch(&data[start..=i]))
+        {
+            // Removing values from the moments leaves rounding errors, so the results are close to
+            // the batch values rather than as close as the other tests require.
+            Some(Ok(e)) => assert!((v - e).abs() <= 1e-10 * f64::max(1.0, e.abs())),
+            _ => assert!(v.is_nan()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_rolling() {
+    let s: Vec<f64> = (0..40).map(|i| f64::from(i * i % 17) - 4.5).collect();
+    for window in [1, 2, 3, 4, 7, 40] {
+        check_rolling(&s, window, batch::rolling_sum, batch::sum);
+        check_rolling(&s, window, batch::rolling_mean, batch::mean);
+        check_rolling(&s, window, batch::rolling_min, batch::min);
+        check_rolling(&s, window, batch::rolling_max, batch::max);
+        check_rolling(
+            &s,
+            window,
+            batch::rolling_population_variance,
+            batch::population_variance,
+        );
+        check_rolling(
+            &s,
+            window,
+            batch::rolling_sample_variance,
+            batch::sample_variance,
+        );
+        check_rolling(
+            &s,
+            window,
+            batch::rolling_population_standard_deviation,
+            batch::population_standard_deviation,
+        );
+        check_rolling(
+            &s,
+            window,
+            batch::rolling_sample_standard_deviation,
+            batch::sample_standard_deviation,
+        );
+        check_rolling(
+            &s,
+            window,
+            batch::rolling_population_skewness,
+            batch::population_skewness,
+        );
+        check_rolling(
+            &s,
+            window,
+            batch::rolling_sample_skewness,
+            batch::sample_skewness,
+        );
+        check_rolling(
+            &s,
+            window,
+            batch::rolling_population_kurtosis,
+            batch::population_kurtosis,
+        );
+        check_rolling(
+            &s,
+            window,
+            batch::rolling_sample_kurtosis,
+            batch::sample_kurtosis,
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_rolling_align() {
+    let a = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0];
+    let nan = f64::NAN;
+    let check = |actual: Vec<f64>, expected: [f64; 6]| {
+        for (v, e) in actual.iter().zip(expected) {
+            if e.is_nan() {
+                assert!(v.is_nan());
+            } else {
+                chk!(*v, e);
+            }
+        }
+    };
+    check(
+        batch::rolling_sum(&a, 3, batch::Align::Right).unwrap(),
+        [nan, nan, 8.0, 6.0, 10.0, 15.0],
+    );
+    check(
+        batch::rolling_sum(&a, 3, batch::Align::Center).unwrap(),
+        [nan, 8.0, 6.0, 10.0, 15.0, nan],
+    );
+    check(
+        batch::rolling_sum(&a, 3, batch::Align::Left).unwrap(),
+        [8.0, 6.0, 10.0, 15.0, nan, nan],
+    );
+    check(
+        batch::rolling_max(&a, 4, batch::Align::Center).unwrap(),
+        [nan, nan, 4.0, 5.0, 9.0, nan],
+    );
+    check(
+        batch::rolling_min(&a, 2, batch::Align::Left).unwrap(),
+        [1.0, 1.0, 1.0, 1.0, 5.0, nan],
+    );
+    check(
+        batch::rolling_mean(&a, 7, batch::Align::Right).unwrap(),
+        [nan; 6],
+    );
+
+    // A window of equal values has no variance, however it was reached.
+    let b = [0.1, 0.7, 0.3, 0.3, 0.3, 0.3, 2.0];
+    let v = batch::rolling_population_variance(&b, 3, batch::Align::Right).unwrap();
+    assert_eq!(v[4], 0.0);
+    assert_eq!(v[5], 0.0);
+    let k = batch::rolling_sample_skewness(&b, 3, batch::Align::Right).unwrap();
+    assert!(k[4].is_nan());
+    chk!(k[6], 1.7320508075688772);
+
+    assert_eq!(
+        batch::rolling_mean(&a, 0, batch::Align::Right),
+        Err(InvalidParameter)
+    );
+    assert_eq!(
+        batch::rolling_max(&[1.0, f64::NAN], 1, batch::Align::Right),
+        Err(InvalidData)
+    );
+    assert_eq!(batch::rolling_mean(&[], 2, batch::Align::Right), Ok(vec![]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_cumulative_stats() {
+    let s: Vec<f64> = (0..20).map(|i| f64::from(i * i % 17) - 4.5).collect();
+    let cumulative = batch::cumulative_stats(&s).unwrap();
+    assert_eq!(cumulative.len(), s.len());
+    let check = |actual: f64, expected: crate::error::Result<f64>| match expected {
+        Ok(e) => chk!(actual, e),
+        Err(_) => assert!(actual.is_nan()),
+    };
+    // The same as incr Stats of each prefix of the data.
+    for (i, c) in cumulative.iter().enumerate() {
+        let mut stats = Stats::new();
+        stats.array_update(&s[..=i]).unwrap();
+        assert_eq!(c.count, stats.count());
+        check(c.sum, stats.sum());
+        check(c.min, stats.min());
+        check(c.max, stats.max());
+        check(c.mean, stats.mean());
+        check(c.population_variance, stats.population_variance());
+        check(c.sample_variance, stats.sample_variance());
+        check(c.population_skewness, stats.population_skewness());
+        check(c.sample_skewness, stats.sample_skewness());
+        check(c.population_kurtosis, stats.population_kurtosis());
+        check(c.sample_kurtosis, stats.sample_kurtosis());
+    }
+    chk!(
+        Ok(cumulative[19].sample_kurtosis),
+        batch::sample_kurtosis(&s)
+    );
+
+    // Undefined statistics are NaN.
+    let c = batch::cumulative_stats(&ONES[..3]).unwrap();
+    chk!(c[0].mean, 1.0);
+    assert!(c[0].sample_variance.is_nan());
+    chk!(c[2].sample_variance, 0.0);
+    assert!(c[2].population_skewness.is_nan());
+
+    assert_eq!(batch::cumulative_stats(&[]), Ok(vec![]));
+    assert_eq!(
+        batch::cumulative_stats(&[1.0, f64::INFINITY]),
+        Err(InvalidData)
+    );
+}
@@ -0,0 +1,159 @@
+use crate::change::{Change, Cusum, Direction, Monitor, PageHinkley};
+use crate::chk;
+use crate::error::StatsError::{InvalidData, InvalidParameter};
+#[cfg(not(feature = "std"))]
+use std::vec::Vec;
+
+static PATTERN: [f64; 5] = [0.0, 1.0, -1.0, 0.5, -0.5];
+
+// Cycles of the pattern around each of the levels in turn.
+fn levels(levels: &[(f64, f64, usize)]) -> Vec<f64> {
+    let mut data = Vec::new();
+    for &(level, spread, cycles) in levels {
+        for _ in 0..cycles {
+            data.extend(PATTERN.iter().map(|p| level + spread * p));
+        }
+    }
+    data
+}
+
+fn changes(mut update: impl FnMut(f64) -> Option<Change>, data: &[f64]) -> Vec<Change> {
+    data.iter().filter_map(|x| update(*x)).collect()
+}
+
+#[test]
+fn test_cusum_mean() {
+    let data = levels(&[(10.0, 1.0, 4), (12.0, 1.0, 6), (9.0, 1.0, 6)]);
+    let mut c = Cusum::new(Monitor::Mean, 20, 0.5, 5.0).unwrap();
+    let found = changes(|x| c.update(x).unwrap(), &data);
+    assert_eq!(
+        found,
+        [
+            Change {
+                time: 22,
+                start: 21,
+                direction: Direction::Increase
+            },
+            Change {
+                time: 52,
+                start: 50,
+                direction: Direction::Decrease
+            },
+        ]
+    );
+    assert_eq!(c.time(), 80);
+    assert_eq!(c.stats().count(), 28);
+    let (mean, sd) = c.baseline().unwrap();
+    chk!(mean, 9.0);
+    chk!(sd, f64::sqrt(10.0 / 19.0));
+
+    // Without a shift, nothing is detected.
+    let mut c = Cusum::new(Monitor::Mean, 10, 0.5, 5.0).unwrap();
+    assert_eq!(
+        changes(|x| c.update(x).unwrap(), &levels(&[(10.0, 1.0, 20)])),
+        []
+    );
+    assert_eq!(c.stats().count(), 100);
+}
+
+#[test]
+fn test_cusum_variance() {
+    let data = levels(&[(10.0, 1.0, 4), (10.0, 3.0, 4)]);
+    let mut c = Cusum::new(Monitor::Variance, 20, 0.5, 5.0).unwrap();
+    let found = changes(|x| c.update(x).unwrap(), &data);
+    assert_eq!(
+        found,
+        [Change {
+            time: 22,
+            start: 22,
+            direction: Direction::Increase
+        }]
+    );
+    // The mean doesn't change, so a mean detector misses it.
+    let mut c = Cusum::new(Monitor::Mean, 20, 0.5, 5.0).unwrap();
+    assert_eq!(changes(|x| c.update(x).unwrap(), &data), []);
+}
+
+#[test]
+fn test_cusum_warmup() {
+    // The baseline waits for some variance.
+    let mut c = Cusum::new(Monitor::Mean, 2, 0.5, 5.0).unwrap();
+    for _ in 0..5 {
+        assert_eq!(c.update(1.0), Ok(None));
+    }
+    assert_eq!(c.baseline(), None);
+    assert_eq!(c.update(2.0), Ok(None));
+    assert!(c.baseline().is_some());
+    c.reset();
+    assert_eq!(c.baseline(), None);
+    assert_eq!(c.stats().count(), 0);
+    assert_eq!(c.time(), 6);
+}
+
+#[test]
+fn test_page_hinkley() {
+    let data = levels(&[(10.0, 1.0, 4), (12.0, 1.0, 6), (9.0, 1.0, 6)]);
+    let mut p = PageHinkley::new(10, 0.1, 5.0).unwrap();
+    let found = changes(|x| p.update(x).unwrap(), &data);
+    assert_eq!(
+        found,
+        [
+            Change {
+                time: 23,
+                start: 21,
+                direction: Direction::Increase
+            },
+            Change {
+                time: 52,
+                start: 48,
+                direction: Direction::Decrease
+            },
+        ]
+    );
+    assert_eq!(p.time(), 80);
+    assert_eq!(p.stats().count(), 28);
+    chk!(p.stats().mean(), Ok(9.0 - 1.0 / 28.0));
+
+    let mut p = PageHinkley::new(10, 0.1, 5.0).unwrap();
+    assert_eq!(
+        changes(|x| p.update(x).unwrap(), &levels(&[(10.0, 1.0, 20)])),
+        []
+    );
+    p.reset();
+    assert_eq!(p.stats().count(), 0);
+}
+
+#[test]
+fn test_change_errors() {
+    assert!(matches!(
+        Cusum::new(Monitor::Mean, 1, 0.5, 5.0),
+        Err(InvalidParameter)
+    ));
+    assert!(matches!(
+        Cusum::new(Monitor::Mean, 10, -0.5, 5.0),
+        Err(InvalidParameter)
+    ));
+    assert!(matches!(
+        Cusum::new(Monitor::Mean, 10, 0.5, 0.0),
+        Err(InvalidParameter)
+    ));
+    assert!(matches!(
+        Cusum::new(Monitor::Mean, 10, f64::NAN, 5.0),
+        Err(InvalidParameter)
+    ));
+    assert!(matches!(
+        PageHinkley::new(10, -0.1, 5.0),
+        Err(InvalidParameter)
+    ));
+    assert!(matches!(
+        PageHinkley::new(10, 0.1, f64::INFINITY),
+        Err(InvalidParameter)
+    ));
+
+    let mut c = Cusum::new(Monitor::Mean, 10, 0.5, 5.0).unwrap();
+    assert_eq!(c.update(f64::NAN), Err(InvalidData));
+    let mut p = PageHinkley::new(10, 0.1, 5.0).unwrap();
+    assert_eq!(p.update(f64::INFINITY), Err(InvalidData));
+    assert_eq!(c.time(), 0);
+    assert_eq!(p.time(), 0);
+}
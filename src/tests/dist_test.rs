@@ -0,0 +1,103 @@
+use crate::chk;
+use crate::dist;
+use crate::error::StatsError::{InvalidData, InvalidParameter};
+
+// Test the distribution functions. The expected values were calculated with R and confirmed with
+// arbitrary precision arithmetic.
+
+#[test]
+fn test_normal_cdf() {
+    chk!(dist::normal_cdf(0.0), Ok(0.5));
+    chk!(dist::normal_cdf(-1.96), Ok(0.024997895148220435));
+    chk!(dist::normal_cdf(1.0), Ok(0.8413447460685429));
+    chk!(dist::normal_cdf(-10.0), Ok(7.619853024160525e-24));
+    chk!(dist::normal_cdf(f64::NAN), Err(InvalidData));
+}
+
+#[test]
+fn test_normal_quantile() {
+    chk!(dist::normal_quantile(0.5), Ok(0.0));
+    chk!(dist::normal_quantile(0.975), Ok(1.9599639845400543));
+    chk!(dist::normal_quantile(0.05), Ok(-1.6448536269514726));
+    chk!(dist::normal_quantile(1e-10), Ok(-6.361340902404057));
+    chk!(dist::normal_quantile(0.0), Err(InvalidParameter));
+    chk!(dist::normal_quantile(1.0), Err(InvalidParameter));
+    chk!(dist::normal_quantile(f64::NAN), Err(InvalidParameter));
+}
+
+#[test]
+fn test_students_t_cdf() {
+    chk!(dist::students_t_cdf(0.0, 3.0), Ok(0.5));
+    chk!(dist::students_t_cdf(2.0, 5.0), Ok(0.9490302605850708));
+    chk!(dist::students_t_cdf(-1.5, 1.0), Ok(0.18716704181099883));
+    chk!(dist::students_t_cdf(0.5, 2.0), Ok(0.6666666666666666));
+    chk!(dist::students_t_cdf(-3.0, 10.5), Ok(0.006337621925937053));
+    chk!(dist::students_t_cdf(4.0, 30.0), Ok(0.9998090771819581));
+    chk!(dist::students_t_cdf(1.0, 0.0), Err(InvalidParameter));
+    chk!(
+        dist::students_t_cdf(1.0, f64::INFINITY),
+        Err(InvalidParameter)
+    );
+    chk!(dist::students_t_cdf(f64::NAN, 3.0), Err(InvalidData));
+}
+
+#[test]
+fn test_students_t_quantile() {
+    chk!(
+        dist::students_t_quantile(0.975, 9.0),
+        Ok(2.2621571627982053)
+    );
+    chk!(
+        dist::students_t_quantile(0.05, 3.0),
+        Ok(-2.3533634348018238)
+    );
+    chk!(dist::students_t_quantile(0.9, 1.0), Ok(3.0776835371752536));
+    chk!(dist::students_t_quantile(0.01, 2.0), Ok(-6.964556734283274));
+    chk!(
+        dist::students_t_quantile(0.995, 100.0),
+        Ok(2.6258905214380177)
+    );
+    chk!(dist::students_t_quantile(0.0, 9.0), Err(InvalidParameter));
+    chk!(dist::students_t_quantile(0.5, -1.0), Err(InvalidParameter));
+}
+
+#[test]
+fn test_chi_squared_cdf() {
+    chk!(dist::chi_squared_cdf(0.0, 3.0), Ok(0.0));
+    chk!(dist::chi_squared_cdf(3.0, 4.0), Ok(0.4421745996289254));
+    chk!(dist::chi_squared_cdf(0.5, 1.0), Ok(0.5204998778130465));
+    chk!(dist::chi_squared_cdf(20.0, 9.0), Ok(0.9820875954701568));
+    chk!(dist::chi_squared_cdf(100.0, 120.0), Ok(0.09226505195893311));
+    chk!(dist::chi_squared_cdf(1.0, 0.0), Err(InvalidParameter));
+    chk!(dist::chi_squared_cdf(f64::NAN, 3.0), Err(InvalidData));
+}
+
+#[test]
+fn test_chi_squared_quantile() {
+    chk!(
+        dist::chi_squared_quantile(0.025, 9.0),
+        Ok(2.700389499980358)
+    );
+    chk!(
+        dist::chi_squared_quantile(0.975, 9.0),
+        Ok(19.022767798641635)
+    );
+    chk!(dist::chi_squared_quantile(0.5, 1.0), Ok(0.4549364231195728));
+    chk!(dist::chi_squared_quantile(0.99, 2.0), Ok(9.210340371976182));
+    chk!(
+        dist::chi_squared_quantile(0.05, 50.0),
+        Ok(34.76425168350175)
+    );
+    chk!(dist::chi_squared_quantile(1.0, 9.0), Err(InvalidParameter));
+    chk!(dist::chi_squared_quantile(0.5, 0.0), Err(InvalidParameter));
+}
+
+#[test]
+fn test_f_cdf() {
+    chk!(dist::f_cdf(0.0, 3.0, 7.0), Ok(0.0));
+    chk!(dist::f_cdf(2.0, 3.0, 7.0), Ok(0.7973063575133491));
+    chk!(dist::f_cdf(0.5, 10.0, 1.0), Ok(0.187669870869603));
+    chk!(dist::f_cdf(1.0, 0.0, 7.0), Err(InvalidParameter));
+    chk!(dist::f_cdf(1.0, 3.0, -1.0), Err(InvalidParameter));
+    chk!(dist::f_cdf(f64::NAN, 3.0, 7.0), Err(InvalidData));
+}
@@ -0,0 +1,80 @@
+use crate::chk;
+use crate::ecdf::Ecdf;
+use crate::error::StatsError::{InvalidData, InvalidParameter, NotEnoughData, Undefined};
+
+static ONES: [f64; 10] = [1.0; 10];
+static ASCENDING: [f64; 5] = [1.0, 2.0, 3.0, 4.0, 5.0];
+static VALUES: [f64; 10] = [
+    1.0, -2.0, 13.0, 47.0, 115.0, -0.03, -123.4, 23.0, -23.04, 12.3,
+];
+static TIES: [f64; 7] = [3.0, 1.0, 2.0, 3.0, 4.0, 2.0, 3.0];
+
+#[test]
+fn test_ecdf_cdf() {
+    let e = Ecdf::new(&VALUES).unwrap();
+    assert_eq!(e.count(), 10);
+    assert_eq!(e.values()[0], -123.4);
+    assert_eq!(e.values()[9], 115.0);
+    chk!(e.cdf(-200.0), Ok(0.0));
+    chk!(e.cdf(-123.4), Ok(0.1));
+    chk!(e.cdf(0.0), Ok(0.4));
+    chk!(e.cdf(12.3), Ok(0.6));
+    chk!(e.cdf(115.0), Ok(1.0));
+    chk!(e.cdf(f64::INFINITY), Ok(1.0));
+    let t = Ecdf::new(&TIES).unwrap();
+    chk!(t.cdf(2.0), Ok(3.0 / 7.0));
+    chk!(t.cdf(2.5), Ok(3.0 / 7.0));
+    chk!(t.cdf(3.0), Ok(6.0 / 7.0));
+    assert_eq!(e.cdf(f64::NAN), Err(InvalidData));
+}
+
+#[test]
+fn test_ecdf_quantile() {
+    let e = Ecdf::new(&VALUES).unwrap();
+    chk!(e.quantile(0.0), Ok(-123.4));
+    chk!(e.quantile(0.25), Ok(-1.5075));
+    chk!(e.quantile(0.5), Ok(6.65));
+    chk!(e.quantile(0.9), Ok(53.8));
+    chk!(e.quantile(1.0), Ok(115.0));
+    let a = Ecdf::new(&ASCENDING).unwrap();
+    chk!(a.quantile(0.1), Ok(1.4));
+    chk!(a.quantile(0.75), Ok(4.0));
+    assert_eq!(e.quantile(-0.1), Err(InvalidParameter));
+    assert_eq!(e.quantile(1.1), Err(InvalidParameter));
+    assert_eq!(e.quantile(f64::NAN), Err(InvalidParameter));
+}
+
+#[test]
+fn test_ecdf_percentile_rank() {
+    let t = Ecdf::new(&TIES).unwrap();
+    chk!(t.percentile_rank(0.0), Ok(0.0));
+    chk!(t.percentile_rank(1.0), Ok(50.0 / 7.0));
+    chk!(t.percentile_rank(2.5), Ok(300.0 / 7.0));
+    chk!(t.percentile_rank(3.0), Ok(450.0 / 7.0));
+    chk!(t.percentile_rank(4.0), Ok(650.0 / 7.0));
+    chk!(t.percentile_rank(5.0), Ok(100.0));
+    let a = Ecdf::new(&ASCENDING).unwrap();
+    chk!(a.percentile_rank(3.0), Ok(50.0));
+    assert_eq!(t.percentile_rank(f64::NAN), Err(InvalidData));
+}
+
+#[test]
+fn test_ecdf_z_score() {
+    let a = Ecdf::new(&ASCENDING).unwrap();
+    chk!(a.z_score(3.0), Ok(0.0));
+    chk!(a.z_score(5.0), Ok(1.2649110640673518));
+    chk!(a.z_score(-1.0), Ok(-2.5298221281347035));
+    let e = Ecdf::new(&VALUES).unwrap();
+    chk!(e.z_score(0.0), Ok(-0.10594688811788391));
+
+    assert_eq!(a.z_score(f64::NAN), Err(InvalidData));
+    assert_eq!(Ecdf::new(&[1.0]).unwrap().z_score(1.0), Err(NotEnoughData));
+    assert_eq!(Ecdf::new(&ONES).unwrap().z_score(1.0), Err(Undefined));
+}
+
+#[test]
+fn test_ecdf_errors() {
+    assert_eq!(Ecdf::new(&[]), Err(NotEnoughData));
+    assert_eq!(Ecdf::new(&[1.0, f64::NAN]), Err(InvalidData));
+    assert_eq!(Ecdf::new(&[f64::INFINITY]), Err(InvalidData));
+}
@@ -0,0 +1,186 @@
+use crate::chk;
+use crate::error::StatsError::{InvalidData, InvalidParameter, NotEnoughData};
+use crate::hist::{Histogram, Layout};
+use crate::incr::Stats;
+
+static DATA: [f64; 10] = [-1.0, 0.0, 1.5, 2.0, 3.9, 4.0, 7.0, 10.0, 10.5, 12.0];
+
+#[test]
+fn test_fixed_histogram() {
+    let mut h = Histogram::fixed(0.0, 10.0, 5).unwrap();
+    assert_eq!(
+        h.layout(),
+        Layout::Fixed {
+            min: 0.0,
+            max: 10.0,
+            bins: 5
+        }
+    );
+    assert_eq!(h.edges(), &[0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+    h.array_update(&DATA).unwrap();
+    assert_eq!(h.counts(), &[2, 2, 1, 1, 1]);
+    assert_eq!(h.underflow(), 1);
+    assert_eq!(h.overflow(), 2);
+    assert_eq!(h.count(), 10);
+    chk!(h.min(), Ok(-1.0));
+    chk!(h.max(), Ok(12.0));
+}
+
+#[test]
+fn test_log_histogram() {
+    let mut h = Histogram::log(1.0, 1000.0, 3).unwrap();
+    let edges = h.edges();
+    assert_eq!(edges.len(), 4);
+    chk!(edges[0], 1.0);
+    chk!(edges[1], 10.0);
+    chk!(edges[2], 100.0);
+    chk!(edges[3], 1000.0);
+    h.array_update(&[1.0, 5.0, 10.0, 50.0, 99.0, 500.0, 999.0, 1000.0])
+        .unwrap();
+    assert_eq!(h.counts(), &[2, 3, 3]);
+    assert_eq!(h.underflow(), 0);
+    assert_eq!(h.overflow(), 0);
+}
+
+#[test]
+fn test_hdr_histogram() {
+    // With one significant digit, each power of two is split into 16 buckets.
+    let mut h = Histogram::hdr(1.0, 100.0, 1).unwrap();
+    let edges = h.edges();
+    assert_eq!(edges.len(), 6 * 16 + 9 + 1);
+    chk!(edges[1], 1.0625);
+    chk!(edges[16], 2.0);
+    chk!(edges[17], 2.125);
+    chk!(edges[104], 96.0);
+    chk!(edges[105], 100.0);
+    for w in edges.windows(2) {
+        assert!((w[1] - w[0]) / w[0] <= 0.1);
+    }
+
+    h.array_update(&[1.0, 1.99, 2.0, 3.3, 97.0, 100.0]).unwrap();
+    let counts = h.counts();
+    assert_eq!(counts[0], 1);
+    assert_eq!(counts[15], 1);
+    assert_eq!(counts[16], 1);
+    assert_eq!(counts[26], 1);
+    assert_eq!(counts[104], 2);
+    assert_eq!(counts.iter().sum::<u32>(), 6);
+
+    // Every value lands in the bucket whose edges surround it.
+    let mut h = Histogram::hdr(0.001, 1000.0, 3).unwrap();
+    for i in 0..1000 {
+        let x = 0.001 * f64::powf(1.0137, i as f64);
+        if x > 1000.0 {
+            break;
+        }
+        let before = h.counts().to_vec();
+        h.update(x).unwrap();
+        let bucket = h
+            .counts()
+            .iter()
+            .zip(&before)
+            .position(|(a, b)| a != b)
+            .unwrap();
+        assert!(h.edges()[bucket] <= x && x <= h.edges()[bucket + 1]);
+    }
+}
+
+#[test]
+fn test_histogram_quantile() {
+    let mut h = Histogram::fixed(0.0, 10.0, 5).unwrap();
+    assert_eq!(h.quantile(0.5), Err(NotEnoughData));
+    h.array_update(&DATA).unwrap();
+    chk!(h.quantile(0.0), Ok(-1.0));
+    chk!(h.quantile(0.05), Ok(-0.5));
+    chk!(h.quantile(0.25), Ok(1.5));
+    chk!(h.quantile(0.5), Ok(4.0));
+    chk!(h.quantile(0.95), Ok(11.5));
+    chk!(h.quantile(1.0), Ok(12.0));
+    assert_eq!(h.quantile(-0.1), Err(InvalidParameter));
+    assert_eq!(h.quantile(1.1), Err(InvalidParameter));
+    assert_eq!(h.quantile(f64::NAN), Err(InvalidParameter));
+
+    // A single value is every quantile.
+    let mut h = Histogram::fixed(0.0, 10.0, 5).unwrap();
+    h.update(3.0).unwrap();
+    chk!(h.quantile(0.0), Ok(3.0));
+    chk!(h.quantile(0.5), Ok(3.0));
+    chk!(h.quantile(1.0), Ok(3.0));
+}
+
+#[test]
+fn test_histogram_cdf() {
+    let mut h = Histogram::fixed(0.0, 10.0, 5).unwrap();
+    assert_eq!(h.cdf(1.0), Err(NotEnoughData));
+    h.array_update(&DATA).unwrap();
+    chk!(h.cdf(-2.0), Ok(0.0));
+    chk!(h.cdf(-0.5), Ok(0.05));
+    chk!(h.cdf(4.0), Ok(0.5));
+    chk!(h.cdf(5.0), Ok(0.55));
+    chk!(h.cdf(11.0), Ok(0.9));
+    chk!(h.cdf(12.0), Ok(1.0));
+    chk!(h.cdf(100.0), Ok(1.0));
+    assert_eq!(h.cdf(f64::NAN), Err(InvalidData));
+}
+
+#[test]
+fn test_histogram_merge() {
+    let mut all = Histogram::log(0.1, 100.0, 12).unwrap();
+    all.array_update(&DATA).unwrap();
+    let mut a = Histogram::log(0.1, 100.0, 12).unwrap();
+    a.array_update(&DATA[..4]).unwrap();
+    let mut b = Histogram::log(0.1, 100.0, 12).unwrap();
+    b.array_update(&DATA[4..]).unwrap();
+    a.merge(&b).unwrap();
+    assert_eq!(a, all);
+
+    // Merging an empty histogram, or into one, changes nothing.
+    let empty = Histogram::log(0.1, 100.0, 12).unwrap();
+    a.merge(&empty).unwrap();
+    assert_eq!(a, all);
+    let mut c = empty.clone();
+    c.merge(&all).unwrap();
+    assert_eq!(c, all);
+
+    let mut other = Histogram::log(0.1, 100.0, 10).unwrap();
+    assert_eq!(other.merge(&all), Err(InvalidParameter));
+}
+
+#[test]
+fn test_histogram_errors() {
+    assert_eq!(Histogram::fixed(1.0, 1.0, 5), Err(InvalidParameter));
+    assert_eq!(Histogram::fixed(2.0, 1.0, 5), Err(InvalidParameter));
+    assert_eq!(Histogram::fixed(0.0, 1.0, 0), Err(InvalidParameter));
+    assert_eq!(Histogram::fixed(f64::NAN, 1.0, 5), Err(InvalidParameter));
+    assert_eq!(
+        Histogram::fixed(0.0, f64::INFINITY, 5),
+        Err(InvalidParameter)
+    );
+    assert_eq!(Histogram::fixed(-1e308, 1e308, 5), Err(InvalidParameter));
+    assert_eq!(Histogram::log(0.0, 1.0, 5), Err(InvalidParameter));
+    assert_eq!(Histogram::hdr(-1.0, 1.0, 2), Err(InvalidParameter));
+    assert_eq!(Histogram::hdr(1.0, 10.0, 0), Err(InvalidParameter));
+    assert_eq!(Histogram::hdr(1.0, 10.0, 6), Err(InvalidParameter));
+
+    let mut h = Histogram::fixed(0.0, 1.0, 5).unwrap();
+    assert_eq!(h.update(f64::NAN), Err(InvalidData));
+    assert_eq!(h.update(f64::INFINITY), Err(InvalidData));
+    assert_eq!(h.count(), 0);
+    assert_eq!(h.min(), Err(NotEnoughData));
+    assert_eq!(h.max(), Err(NotEnoughData));
+}
+
+#[test]
+fn test_stats_with_histogram() {
+    assert!(Stats::new().histogram().is_none());
+
+    let mut d = Stats::with_histogram(Histogram::fixed(0.0, 10.0, 5).unwrap());
+    d.array_update(&DATA).unwrap();
+    assert_eq!(d.update(f64::NAN), Err(InvalidData));
+    let h = d.histogram().unwrap();
+    assert_eq!(h.count(), d.count());
+    assert_eq!(h.counts(), &[2, 2, 1, 1, 1]);
+    chk!(h.min(), d.min());
+    chk!(h.max(), d.max());
+    chk!(d.mean(), Ok(4.99));
+}
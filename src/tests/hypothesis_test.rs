@@ -0,0 +1,158 @@
+use crate::chk;
+use crate::error::StatsError::{InvalidData, InvalidParameter, NotEnoughData};
+use crate::hypothesis;
+#[cfg(not(feature = "std"))]
+use std::vec::Vec;
+
+// Test the nonparametric tests. The expected values were calculated by enumerating every
+// arrangement of the ranks for the exact p-values, and with arbitrary precision arithmetic for the
+// approximations.
+
+static X: [f64; 9] = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+static Y: [f64; 9] = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.14, 1.29];
+
+#[test]
+fn test_mann_whitney_u() {
+    let r =
+        hypothesis::mann_whitney_u(&[1.1, 2.2, 3.3, 4.4], &[0.5, 2.5, 3.9, 5.1, 6.0, 7.2]).unwrap();
+    chk!(r.statistic, 7.0);
+    chk!(r.p_value, 0.3523809523809524);
+
+    let r = hypothesis::mann_whitney_u(&[1.0, 2.0, 3.0, 4.0, 5.0], &[6.0, 7.0, 8.0, 9.0, 10.0])
+        .unwrap();
+    chk!(r.statistic, 0.0);
+    chk!(r.p_value, 0.007936507936507936);
+
+    // Ties
+    let r = hypothesis::mann_whitney_u(&X, &Y).unwrap();
+    chk!(r.statistic, 58.0);
+    chk!(r.p_value, 0.1329194581853188);
+    let r = hypothesis::mann_whitney_u(&[1.0, 2.0, 2.0, 3.0, 4.0], &[2.0, 3.0, 3.0, 5.0, 6.0, 7.0])
+        .unwrap();
+    chk!(r.statistic, 6.0);
+    chk!(r.p_value, 0.11390314458853065);
+    // A statistic at its mean isn't moved by the continuity correction.
+    let r = hypothesis::mann_whitney_u(&[1.0, 2.0, 3.0, 3.0], &[1.0, 2.0, 3.0, 3.0]).unwrap();
+    chk!(r.statistic, 8.0);
+    chk!(r.p_value, 1.0);
+
+    // Large samples
+    let x: Vec<f64> = (0..55).map(|i| 3.0 * i as f64).collect();
+    let y: Vec<f64> = (0..52).map(|i| 4.0 * i as f64 + 0.5).collect();
+    let r = hypothesis::mann_whitney_u(&x, &y).unwrap();
+    chk!(r.statistic, 1134.0);
+    chk!(r.p_value, 0.06549774214370929);
+    let y: Vec<f64> = (0..52).map(|i| 4.0 * i as f64 + 1.0).collect();
+    let r = hypothesis::mann_whitney_u(&x, &y).unwrap();
+    chk!(r.statistic, 1127.5);
+    chk!(r.p_value, 0.059779029594163945);
+
+    assert_eq!(hypothesis::mann_whitney_u(&[], &Y), Err(NotEnoughData));
+    assert_eq!(hypothesis::mann_whitney_u(&X, &[]), Err(NotEnoughData));
+    assert_eq!(
+        hypothesis::mann_whitney_u(&[1.0, f64::NAN], &[f64::NAN, 2.0]),
+        Err(InvalidData)
+    );
+    assert_eq!(
+        hypothesis::mann_whitney_u(&X, &[1.0, f64::INFINITY]),
+        Err(InvalidData)
+    );
+}
+
+#[test]
+fn test_wilcoxon_signed_rank() {
+    let r = hypothesis::wilcoxon_signed_rank(&X, &Y).unwrap();
+    chk!(r.statistic, 40.0);
+    chk!(r.p_value, 0.0390625);
+    let r = hypothesis::wilcoxon_signed_rank(&Y, &X).unwrap();
+    chk!(r.statistic, 5.0);
+    chk!(r.p_value, 0.0390625);
+
+    // Ties and zero differences
+    let r = hypothesis::wilcoxon_signed_rank(
+        &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+        &[0.0, 2.0, 1.0, 5.0, 3.0, 3.0],
+    )
+    .unwrap();
+    chk!(r.statistic, 13.5);
+    chk!(r.p_value, 0.13441657417036026);
+    let r =
+        hypothesis::wilcoxon_signed_rank(&[1.0, 2.0, 3.0, 4.0, 0.0], &[2.0, 1.0, 4.0, 3.0, 0.0])
+            .unwrap();
+    chk!(r.statistic, 5.0);
+    chk!(r.p_value, 1.0);
+
+    // Large samples
+    let x: Vec<f64> = (0..60).map(|i| (i * i % 97) as f64).collect();
+    let y: Vec<f64> = (0..60).map(|i| (7 * i % 89) as f64 + 0.25).collect();
+    let r = hypothesis::wilcoxon_signed_rank(&x, &y).unwrap();
+    chk!(r.statistic, 1090.0);
+    chk!(r.p_value, 0.19890329048947425);
+
+    assert_eq!(
+        hypothesis::wilcoxon_signed_rank(&X, &Y[1..]),
+        Err(InvalidParameter)
+    );
+    assert_eq!(hypothesis::wilcoxon_signed_rank(&X, &X), Err(NotEnoughData));
+    assert_eq!(
+        hypothesis::wilcoxon_signed_rank(&[], &[]),
+        Err(NotEnoughData)
+    );
+    assert_eq!(
+        hypothesis::wilcoxon_signed_rank(&[1.0, f64::NAN], &[2.0, 3.0]),
+        Err(InvalidData)
+    );
+}
+
+#[test]
+fn test_kolmogorov_smirnov() {
+    let x = [0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56];
+    let y = [2.2, 1.5, 3.1, 0.4, 2.8, 1.9, 0.95];
+    let r = hypothesis::kolmogorov_smirnov(&x, &y).unwrap();
+    chk!(r.statistic, 0.8571428571428571);
+    chk!(r.p_value, 0.004662004662004662);
+    let r = hypothesis::kolmogorov_smirnov(&y, &x).unwrap();
+    chk!(r.statistic, 0.8571428571428571);
+    chk!(r.p_value, 0.004662004662004662);
+
+    // Ties
+    let r = hypothesis::kolmogorov_smirnov(
+        &[1.0, 2.0, 2.0, 3.0, 4.0, 5.0],
+        &[2.0, 3.0, 3.0, 4.0, 6.0, 7.0, 8.0],
+    )
+    .unwrap();
+    chk!(r.statistic, 0.42857142857142855);
+    chk!(r.p_value, 0.5930795499453455);
+
+    // Large samples
+    let x: Vec<f64> = (0..120).map(|i| 3.0 * i as f64).collect();
+    let y: Vec<f64> = (0..100).map(|i| 4.0 * i as f64 + 0.5).collect();
+    let r = hypothesis::kolmogorov_smirnov(&x, &y).unwrap();
+    chk!(r.statistic, 0.105);
+    chk!(r.p_value, 0.5845066737049734);
+
+    // Large samples with a tiny D, where the terms of the asymptotic series underflow.
+    let x: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+    let y: Vec<f64> = (0..400).map(|i| 2.5 * i as f64 + 0.5).collect();
+    let r = hypothesis::kolmogorov_smirnov(&x, &y).unwrap();
+    chk!(r.statistic, 0.0015);
+    chk!(r.p_value, 1.0);
+    let r =
+        hypothesis::kolmogorov_smirnov(&x, &x.iter().map(|v| v + 0.5).collect::<Vec<_>>()).unwrap();
+    chk!(r.statistic, 0.001);
+    chk!(r.p_value, 1.0);
+
+    // Large disjoint samples, where the terms of the asymptotic series underflow.
+    let x: Vec<f64> = (0..800).map(|i| i as f64).collect();
+    let y: Vec<f64> = (0..800).map(|i| 10000.0 + i as f64).collect();
+    let r = hypothesis::kolmogorov_smirnov(&x, &y).unwrap();
+    chk!(r.statistic, 1.0);
+    chk!(r.p_value, 0.0);
+
+    assert_eq!(hypothesis::kolmogorov_smirnov(&[], &y), Err(NotEnoughData));
+    assert_eq!(hypothesis::kolmogorov_smirnov(&x, &[]), Err(NotEnoughData));
+    assert_eq!(
+        hypothesis::kolmogorov_smirnov(&[1.0, f64::NAN], &[f64::NAN, 2.0]),
+        Err(InvalidData)
+    );
+}
@@ -1,6 +1,12 @@
+#[cfg(feature = "alloc")]
+use crate::batch;
 use crate::chk;
-use crate::error::StatsError::{InvalidData, NotEnoughData, Undefined};
-use crate::incr::Stats;
+use crate::error::StatsError::{InvalidData, InvalidParameter, NotEnoughData, Undefined};
+#[cfg(feature = "alloc")]
+use crate::hist::Histogram;
+use crate::incr::{self, Moments, Stats, StatsExt};
+#[cfg(not(feature = "std"))]
+use std::vec::Vec;
 
 // Test the incremtal functions. Update the descriptive stats one point at a time.
 static ZEROS: [f64; 10] = [0.0; 10];
@@ -459,3 +465,494 @@ fn test_update10() {
     chk!(d.population_kurtosis(), Ok(1.253240236214162));
     chk!(d.sample_kurtosis(), Ok(3.179835417592894));
 }
+
+#[test]
+// Confidence intervals for the mean (Student's t) and the variance and standard deviation
+// (chi-squared).
+fn test_confidence_intervals() {
+    let mut d = Stats::new();
+    d.array_update(&VALUES).unwrap();
+    let (lower, upper) = d.mean_confidence_interval(0.95).unwrap();
+    chk!(lower, -36.14002459334809);
+    chk!(upper, 48.70602459334808);
+    let (lower, upper) = d.variance_confidence_interval(0.95).unwrap();
+    chk!(lower, 1663.8972806186584);
+    chk!(upper, 11721.24673504701);
+    let (lower, upper) = d.standard_deviation_confidence_interval(0.95).unwrap();
+    chk!(lower, 40.79089703130661);
+    chk!(upper, 108.26470678409936);
+    let (lower, upper) = d.mean_confidence_interval(0.9).unwrap();
+    chk!(lower, -28.094008062634362);
+    chk!(upper, 40.66000806263436);
+    let (lower, upper) = d.standard_deviation_confidence_interval(0.9).unwrap();
+    chk!(lower, 43.25268292812368);
+    chk!(upper, 97.56564391412302);
+
+    let mut d = Stats::new();
+    d.array_update(&ASCENDING).unwrap();
+    let (lower, upper) = d.mean_confidence_interval(0.99).unwrap();
+    chk!(lower, -0.25558670475778533);
+    chk!(upper, 6.2555867047577856);
+    let (lower, upper) = d.variance_confidence_interval(0.99).unwrap();
+    chk!(lower, 0.6729357812419683);
+    chk!(upper, 48.31172421258249);
+
+    // The level must be a probability strictly between 0 and 1.
+    assert_eq!(d.mean_confidence_interval(0.0), Err(InvalidParameter));
+    assert_eq!(d.mean_confidence_interval(1.0), Err(InvalidParameter));
+    assert_eq!(d.variance_confidence_interval(-0.5), Err(InvalidParameter));
+    assert_eq!(
+        d.standard_deviation_confidence_interval(f64::NAN),
+        Err(InvalidParameter)
+    );
+
+    // At least two values are needed to estimate the spread.
+    let mut d = Stats::new();
+    d.update(1.0).unwrap();
+    assert_eq!(d.mean_confidence_interval(0.95), Err(NotEnoughData));
+    assert_eq!(d.variance_confidence_interval(0.95), Err(NotEnoughData));
+    assert_eq!(
+        d.standard_deviation_confidence_interval(0.95),
+        Err(NotEnoughData)
+    );
+}
+
+#[test]
+// The Jarque-Bera and D'Agostino K-squared normality tests.
+fn test_normality_tests() {
+    let mut d = Stats::new();
+    d.array_update(&VALUES).unwrap();
+    let r = d.jarque_bera().unwrap();
+    chk!(r.statistic, 1.0336992860361678);
+    chk!(r.p_value, 0.5963964532635635);
+    let r = d.dagostino_k_squared().unwrap();
+    chk!(r.statistic, 4.423738209435052);
+    chk!(r.p_value, 0.1094957980190081);
+
+    // D'Agostino's transformations require at least 8 values.
+    let mut d = Stats::new();
+    d.array_update(&VALUES[..7]).unwrap();
+    assert!(d.jarque_bera().is_ok());
+    assert_eq!(d.dagostino_k_squared(), Err(NotEnoughData));
+
+    // The tests are undefined if all of the values are the same.
+    let mut d = Stats::new();
+    d.array_update(&ONES).unwrap();
+    assert_eq!(d.jarque_bera(), Err(Undefined));
+    assert_eq!(d.dagostino_k_squared(), Err(Undefined));
+}
+
+#[test]
+// Two-sample t-tests and F-test, comparing VALUES with ASCENDING and ASCENDING with a third sample.
+fn test_two_sample_tests() {
+    let mut v = Stats::new();
+    v.array_update(&VALUES).unwrap();
+    let mut a = Stats::new();
+    a.array_update(&ASCENDING).unwrap();
+    let mut b = Stats::new();
+    b.array_update(&[2.1, 3.3, 1.9, 4.8, 2.6, 3.9, 5.2, 3.1])
+        .unwrap();
+    let r = v.students_t_test(&a).unwrap();
+    chk!(r.statistic, 0.1214543931242461);
+    chk!(r.df, 13.0);
+    chk!(r.p_value, 0.9051884162750196);
+    let r = v.welch_t_test(&a).unwrap();
+    chk!(r.statistic, 0.17493774580825308);
+    chk!(r.df, 9.025567999662424);
+    chk!(r.p_value, 0.8649900493720019);
+    let r = v.f_test(&a).unwrap();
+    chk!(r.statistic, 1406.752516);
+    chk!(r.df_numerator, 9.0);
+    chk!(r.df_denominator, 4.0);
+    chk!(r.p_value, 2.467063413869685e-6);
+
+    let r = a.students_t_test(&b).unwrap();
+    chk!(r.statistic, -0.4701778610985646);
+    chk!(r.df, 11.0);
+    chk!(r.p_value, 0.6474121307543849);
+    let r = a.welch_t_test(&b).unwrap();
+    chk!(r.statistic, -0.43937232578122587);
+    chk!(r.df, 6.8986359843262415);
+    chk!(r.p_value, 0.6738251081465415);
+    let r = a.f_test(&b).unwrap();
+    chk!(r.statistic, 1.7294626312538603);
+    chk!(r.p_value, 0.4943891303962648);
+    // The two-sided p-value doesn't depend on the order of the samples.
+    let r = b.f_test(&a).unwrap();
+    chk!(r.statistic, 0.5782142857142857);
+    chk!(r.p_value, 0.4943891303962648);
+
+    // Each sample needs at least two values, and the tests are undefined without any variance.
+    let mut one = Stats::new();
+    one.update(1.0).unwrap();
+    let mut ones = Stats::new();
+    ones.array_update(&ONES).unwrap();
+    assert_eq!(a.welch_t_test(&one), Err(NotEnoughData));
+    assert_eq!(one.students_t_test(&a), Err(NotEnoughData));
+    assert_eq!(a.f_test(&one), Err(NotEnoughData));
+    assert_eq!(ones.welch_t_test(&ones), Err(Undefined));
+    assert_eq!(ones.students_t_test(&ones), Err(Undefined));
+    assert_eq!(a.f_test(&ones), Err(Undefined));
+}
+
+#[test]
+fn test_anova() {
+    let groups = |data: &[&[f64]]| -> Vec<Stats> {
+        data.iter()
+            .map(|d| {
+                let mut s = Stats::new();
+                s.array_update(d).unwrap();
+                s
+            })
+            .collect()
+    };
+    let b = [2.1, 3.3, 1.9, 4.8, 2.6, 3.9, 5.2, 3.1];
+    let c = [3.5, 4.1, 2.9, 5.0, 4.4, 3.8];
+
+    let abc = groups(&[&ASCENDING, &b, &c]);
+    let r = incr::one_way_anova(&abc).unwrap();
+    chk!(r.ss_between, 2.5736184210526316);
+    chk!(r.ss_within, 22.77375);
+    chk!(r.df_between, 2.0);
+    chk!(r.df_within, 16.0);
+    chk!(r.statistic, 0.9040648715482102);
+    chk!(r.p_value, 0.4246326386724951);
+    let r = incr::welch_anova(&abc).unwrap();
+    chk!(r.statistic, 1.071905292114134);
+    chk!(r.df_numerator, 2.0);
+    chk!(r.df_denominator, 8.801891860752443);
+    chk!(r.p_value, 0.38315365278556685);
+
+    let vab = groups(&[&VALUES, &ASCENDING, &b]);
+    let r = incr::one_way_anova(&vab).unwrap();
+    chk!(r.ss_between, 53.326361739130434);
+    chk!(r.ss_within, 31672.05036);
+    chk!(r.statistic, 0.016837041218676074);
+    chk!(r.p_value, 0.9833178317025013);
+    let r = incr::welch_anova(&vab).unwrap();
+    chk!(r.statistic, 0.10287273241068033);
+    chk!(r.df_denominator, 10.41498664840854);
+    chk!(r.p_value, 0.903147127749861);
+
+    // With two groups, the ANOVA is the same as the t-tests.
+    let ab = groups(&[&ASCENDING, &b]);
+    let r = incr::one_way_anova(&ab).unwrap();
+    chk!(r.statistic, 0.22106722106722107);
+    chk!(r.p_value, ab[0].students_t_test(&ab[1]).unwrap().p_value);
+    let r = incr::welch_anova(&ab).unwrap();
+    chk!(r.df_denominator, 6.8986359843262415);
+    chk!(r.p_value, ab[0].welch_t_test(&ab[1]).unwrap().p_value);
+
+    // At least two groups are needed, and the tests are undefined without any variance.
+    assert_eq!(
+        incr::one_way_anova(&groups(&[&ASCENDING])),
+        Err(NotEnoughData)
+    );
+    assert_eq!(
+        incr::one_way_anova(&groups(&[&ASCENDING, &[]])),
+        Err(NotEnoughData)
+    );
+    assert_eq!(
+        incr::one_way_anova(&groups(&[&[1.0], &[2.0]])),
+        Err(NotEnoughData)
+    );
+    assert_eq!(incr::welch_anova(&[]), Err(NotEnoughData));
+    assert_eq!(
+        incr::welch_anova(&groups(&[&ASCENDING, &[1.0]])),
+        Err(NotEnoughData)
+    );
+    assert_eq!(
+        incr::one_way_anova(&groups(&[&ONES, &ONES])),
+        Err(Undefined)
+    );
+    assert_eq!(
+        incr::welch_anova(&groups(&[&ASCENDING, &ONES])),
+        Err(Undefined)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_autocorrelation() {
+    let mut a = incr::Autocorrelation::new(4).unwrap();
+    assert_eq!(a.autocovariance(0), Err(NotEnoughData));
+    a.array_update(&VALUES).unwrap();
+    assert_eq!(a.count(), 10);
+    let covariances = [
+        3165.193161,
+        198.6066921,
+        -1020.0539488,
+        -521.6082377,
+        -393.8369866,
+    ];
+    let correlations = [
+        1.0,
+        0.06274710009712421,
+        -0.3222722585681715,
+        -0.16479507289697445,
+        -0.1244274730062833,
+    ];
+    for lag in 0..=4 {
+        chk!(a.autocovariance(lag), Ok(covariances[lag]));
+        chk!(a.autocorrelation(lag), Ok(correlations[lag]));
+    }
+    let r = a.ljung_box(3).unwrap();
+    chk!(r.statistic, 2.07594281431424);
+    chk!(r.p_value, 0.5567950068766562);
+
+    // The same as the batch autocorrelations, whether or not the series is longer than the max lag.
+    let s: Vec<f64> = (0..30).map(|i| f64::from(i * i % 17)).collect();
+    let mut a = incr::Autocorrelation::new(5).unwrap();
+    for (i, x) in s.iter().enumerate() {
+        a.update(*x).unwrap();
+        let acf = batch::acf(&s[..=i], usize::min(i, 5)).unwrap_or_default();
+        for (lag, r) in acf.iter().enumerate() {
+            chk!(a.autocorrelation(lag), Ok(*r));
+        }
+    }
+    chk!(a.ljung_box(5).unwrap().p_value, 0.4345832077628657);
+
+    assert!(matches!(
+        incr::Autocorrelation::new(0),
+        Err(InvalidParameter)
+    ));
+    assert_eq!(a.autocorrelation(6), Err(InvalidParameter));
+    assert_eq!(a.ljung_box(6).map(|r| r.p_value), Err(InvalidParameter));
+    assert_eq!(a.update(f64::NAN), Err(InvalidData));
+    assert_eq!(a.count(), 30);
+    let mut a = incr::Autocorrelation::new(2).unwrap();
+    a.array_update(&[3.0, 3.0]).unwrap();
+    assert_eq!(a.autocorrelation(1), Err(Undefined));
+    assert_eq!(a.autocorrelation(2), Err(NotEnoughData));
+}
+
+#[test]
+fn test_iterators() {
+    let mut expected = Stats::new();
+    expected.array_update(&VALUES).unwrap();
+
+    // Collect owned or borrowed values, or calculate the stats of an iterator directly.
+    let s: Stats = VALUES.iter().collect();
+    chk!(s.sample_kurtosis(), expected.sample_kurtosis());
+    let s: Stats = VALUES.into_iter().collect();
+    chk!(s.sample_kurtosis(), expected.sample_kurtosis());
+    let s = VALUES.iter().stats().unwrap();
+    chk!(s.sample_kurtosis(), expected.sample_kurtosis());
+    let s = VALUES.iter().map(|x| x * 2.0).stats().unwrap();
+    chk!(s.mean(), Ok(2.0 * expected.mean().unwrap()));
+
+    // Extend in pieces.
+    let mut s: Stats = VALUES[..4].iter().collect();
+    s.extend(&VALUES[4..7]);
+    s.extend(VALUES[7..].iter().copied());
+    assert_eq!(s.count(), 10);
+    chk!(s.sample_kurtosis(), expected.sample_kurtosis());
+
+    // Values parsed from text, stopping at the first error.
+    let text = "1.0 2.0 3.0 4.0 5.0";
+    let s = text
+        .split(' ')
+        .map(|v| v.parse::<f64>().map_err(|_| InvalidData))
+        .collect::<crate::error::Result<Stats>>()
+        .unwrap();
+    chk!(s.sample_variance(), Ok(2.5));
+    let s = "1.0 x 3.0"
+        .split(' ')
+        .map(|v| v.parse::<f64>().map_err(|_| InvalidData))
+        .collect::<crate::error::Result<Stats>>();
+    assert!(matches!(s, Err(InvalidData)));
+
+    let mut s = Stats::new();
+    assert_eq!(s.try_extend([1.0, 2.0, f64::NAN, 4.0]), Err(InvalidData));
+    assert_eq!(s.count(), 2);
+    assert!(matches!(
+        [1.0, f64::INFINITY].iter().stats(),
+        Err(InvalidData)
+    ));
+    assert!(matches!(Vec::<f64>::new().into_iter().stats(), Ok(s) if s.count() == 0));
+}
+
+#[test]
+#[should_panic]
+fn test_extend_with_bad_data() {
+    let mut s = Stats::new();
+    s.extend([1.0, f64::NAN]);
+}
+
+#[test]
+#[should_panic]
+fn test_collect_with_bad_data() {
+    let _: Stats = [1.0, f64::INFINITY].into_iter().collect();
+}
+
+#[test]
+#[should_panic]
+fn test_collect_results_with_bad_data() {
+    // The parse succeeds, so collect() can't return the error.
+    let _ = "1.0 nan"
+        .split(' ')
+        .map(|v| v.parse::<f64>().map_err(|_| InvalidData))
+        .collect::<crate::error::Result<Stats>>();
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_merge() {
+    let mut expected = Stats::new();
+    expected.array_update(&VALUES).unwrap();
+
+    // Merging the stats of the parts, split anywhere, gives the stats of the whole.
+    for split in 0..=VALUES.len() {
+        let mut s: Stats = VALUES[..split].iter().collect();
+        s.merge(&VALUES[split..].iter().collect()).unwrap();
+        assert_eq!(s.count(), 10);
+        chk!(s.min(), expected.min());
+        chk!(s.max(), expected.max());
+        chk!(s.sum(), expected.sum());
+        chk!(s.mean(), expected.mean());
+        chk!(s.sample_variance(), expected.sample_variance());
+        chk!(s.sample_skewness(), expected.sample_skewness());
+        chk!(s.sample_kurtosis(), expected.sample_kurtosis());
+    }
+
+    // Histograms are merged too, and must match.
+    let histogram = Histogram::fixed(-150.0, 150.0, 6).unwrap();
+    let mut a = Stats::with_histogram(histogram.clone());
+    a.array_update(&VALUES[..5]).unwrap();
+    let mut b = Stats::with_histogram(histogram.clone());
+    b.array_update(&VALUES[5..]).unwrap();
+    a.merge(&b).unwrap();
+    assert_eq!(a.histogram().unwrap().counts(), &[1, 0, 3, 5, 0, 1]);
+    chk!(a.sample_kurtosis(), expected.sample_kurtosis());
+    assert_eq!(a.merge(&expected), Err(InvalidParameter));
+    assert_eq!(expected.merge(&b), Err(InvalidParameter));
+    let mut c = Stats::with_histogram(Histogram::fixed(-150.0, 150.0, 3).unwrap());
+    assert_eq!(c.merge(&b), Err(InvalidParameter));
+    assert_eq!(a.count(), 10);
+
+    // Merging into empty stats copies the other stats exactly.
+    let mut s = Stats::new();
+    s.merge(&expected).unwrap();
+    assert_eq!(s.count(), expected.count());
+    assert_eq!(s.mean(), expected.mean());
+    assert_eq!(s.sample_variance(), expected.sample_variance());
+    assert_eq!(s.sample_skewness(), expected.sample_skewness());
+    assert_eq!(s.sample_kurtosis(), expected.sample_kurtosis());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_update_many() {
+    let mut expected = Stats::new();
+    expected.array_update(&VALUES).unwrap();
+    expected.array_update(&ASCENDING).unwrap();
+
+    let mut s = Stats::new();
+    s.update_many(&VALUES).unwrap();
+    s.update_many(&[]).unwrap();
+    s.update_many(&ASCENDING).unwrap();
+    assert_eq!(s.count(), 15);
+    chk!(s.min(), expected.min());
+    chk!(s.max(), expected.max());
+    chk!(s.sum(), expected.sum());
+    chk!(s.mean(), expected.mean());
+    chk!(s.sample_variance(), expected.sample_variance());
+    chk!(s.sample_skewness(), expected.sample_skewness());
+    chk!(s.sample_kurtosis(), expected.sample_kurtosis());
+
+    // A bad value rejects the whole slice.
+    assert_eq!(s.update_many(&[1.0, f64::NAN, 2.0]), Err(InvalidData));
+    assert_eq!(s.update_many(&[f64::NEG_INFINITY]), Err(InvalidData));
+    assert_eq!(s.count(), 15);
+
+    let mut s = Stats::with_histogram(Histogram::fixed(-150.0, 150.0, 6).unwrap());
+    s.update(200.0).unwrap();
+    s.update_many(&VALUES).unwrap();
+    let histogram = s.histogram().unwrap();
+    assert_eq!(histogram.counts(), &[1, 0, 3, 5, 0, 1]);
+    assert_eq!(histogram.overflow(), 1);
+    assert_eq!(s.count(), 11);
+
+    // Finite values whose sum overflows give the same stats as updating them one at a time.
+    let huge = [1e308, 1e308, 1.5e308];
+    let mut expected = Stats::new();
+    expected.array_update(&huge).unwrap();
+    let mut s = Stats::new();
+    s.update_many(&huge).unwrap();
+    assert_eq!(s.count(), 3);
+    chk!(s.mean(), expected.mean());
+    chk!(s.mean(), Ok(1.1666666666666667e308));
+    chk!(s.min(), Ok(1e308));
+    chk!(s.max(), Ok(1.5e308));
+
+    // A slice of several blocks. The expected values were calculated with exact arithmetic.
+    let data: Vec<f64> = (0..10_000).map(|i| f64::from(i % 97 * (i % 13))).collect();
+    let mut s = Stats::new();
+    s.update_many(&data).unwrap();
+    assert_eq!(s.count(), 10_000);
+    chk!(s.min(), Ok(0.0));
+    chk!(s.max(), Ok(1152.0));
+    chk!(s.mean(), Ok(287.6247));
+    chk!(s.sample_variance(), Ok(71383.17676758676));
+    chk!(s.sample_skewness(), Ok(0.9936050052146538));
+    chk!(s.sample_kurtosis(), Ok(0.18946029600330108));
+}
+
+#[test]
+fn test_moments() {
+    let mut full = Stats::new();
+    full.array_update(&VALUES).unwrap();
+    assert_eq!(full.moments(), Moments::Kurtosis);
+
+    let mut mean = Stats::with_moments(Moments::Mean);
+    mean.array_update(&VALUES).unwrap();
+    assert_eq!(mean.count(), 10);
+    chk!(mean.min(), full.min());
+    chk!(mean.max(), full.max());
+    chk!(mean.sum(), full.sum());
+    chk!(mean.mean(), full.mean());
+    assert_eq!(mean.population_variance(), Err(InvalidParameter));
+    assert_eq!(mean.sample_standard_deviation(), Err(InvalidParameter));
+    assert_eq!(mean.sample_skewness(), Err(InvalidParameter));
+    assert!(matches!(
+        mean.variance_confidence_interval(0.95),
+        Err(InvalidParameter)
+    ));
+    assert!(matches!(
+        incr::one_way_anova(&[mean.clone(), full.clone()]),
+        Err(InvalidParameter)
+    ));
+
+    let mut variance = Stats::with_moments(Moments::Variance);
+    variance.array_update(&VALUES).unwrap();
+    chk!(variance.mean(), full.mean());
+    chk!(variance.sample_variance(), full.sample_variance());
+    chk!(
+        variance.population_standard_deviation(),
+        full.population_standard_deviation()
+    );
+    assert_eq!(variance.population_skewness(), Err(InvalidParameter));
+    assert_eq!(variance.sample_kurtosis(), Err(InvalidParameter));
+
+    let mut skewness = Stats::with_moments(Moments::Skewness);
+    skewness.array_update(&VALUES).unwrap();
+    chk!(skewness.sample_variance(), full.sample_variance());
+    chk!(skewness.sample_skewness(), full.sample_skewness());
+    assert_eq!(skewness.population_kurtosis(), Err(InvalidParameter));
+
+    // Merged stats maintain the lower of the two moments.
+    let mut merged = full.clone();
+    merged.merge(&variance).unwrap();
+    assert_eq!(merged.moments(), Moments::Variance);
+    assert_eq!(merged.count(), 20);
+    chk!(
+        merged.sample_variance(),
+        Ok(full.sample_variance().unwrap() * 18.0 / 19.0)
+    );
+    assert_eq!(merged.sample_skewness(), Err(InvalidParameter));
+
+    let mut many = Stats::with_moments(Moments::Variance);
+    many.update_many(&VALUES).unwrap();
+    assert_eq!(many.moments(), Moments::Variance);
+    chk!(many.sample_variance(), full.sample_variance());
+}
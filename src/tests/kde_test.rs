@@ -0,0 +1,118 @@
+use crate::chk;
+use crate::error::StatsError::{InvalidData, InvalidParameter, NotEnoughData, Undefined};
+use crate::kde::{self, Bandwidth, Kde, Kernel};
+
+// The expected values were calculated with arbitrary precision arithmetic by summing the kernels
+// directly.
+static ONES: [f64; 10] = [1.0; 10];
+static ASCENDING: [f64; 5] = [1.0, 2.0, 3.0, 4.0, 5.0];
+static B: [f64; 8] = [2.1, 3.3, 1.9, 4.8, 2.6, 3.9, 5.2, 3.1];
+
+#[test]
+fn test_bandwidth() {
+    chk!(kde::silverman_bandwidth(&B), Ok(0.7139025366155509));
+    chk!(kde::scott_bandwidth(&B), Ok(0.8408185431249822));
+    chk!(kde::silverman_bandwidth(&ASCENDING), Ok(0.9735846228506357));
+    // The IQR limits the bandwidth when there are outliers.
+    chk!(
+        kde::silverman_bandwidth(&[1.0, 2.0, 3.0, 4.0, 100.0]),
+        Ok(0.9735846228506357)
+    );
+    chk!(
+        kde::scott_bandwidth(&[1.0, 2.0, 3.0, 4.0, 100.0]),
+        Ok(1.1466663335796377)
+    );
+    // Without an IQR, the standard deviation is used.
+    chk!(
+        kde::silverman_bandwidth(&[1.0, 1.0, 1.0, 1.0, 2.0]),
+        Ok(0.9 * f64::sqrt(0.2) * f64::powf(5.0, -0.2))
+    );
+
+    assert_eq!(kde::silverman_bandwidth(&[]), Err(NotEnoughData));
+    assert_eq!(kde::silverman_bandwidth(&[1.0]), Err(NotEnoughData));
+    assert_eq!(kde::scott_bandwidth(&ONES), Err(Undefined));
+}
+
+#[test]
+fn test_density() {
+    let g = Kde::new(&B, Kernel::Gaussian, Bandwidth::Silverman).unwrap();
+    let e = Kde::new(&B, Kernel::Epanechnikov, Bandwidth::Silverman).unwrap();
+    chk!(g.bandwidth(), 0.7139025366155509);
+    chk!(g.density(0.0), Ok(0.0030457598470528644));
+    chk!(g.density(2.5), Ok(0.27494884297690336));
+    chk!(g.density(3.3), Ok(0.26620292712889543));
+    chk!(g.density(6.0), Ok(0.055286622500732525));
+    chk!(e.density(0.0), Ok(0.0));
+    chk!(e.density(2.5), Ok(0.2719384656089538));
+    chk!(e.density(3.3), Ok(0.2603758175148855));
+    chk!(e.density(6.0), Ok(0.06952046368876559));
+    let d = e.densities(&[2.5, 6.0]).unwrap();
+    chk!(d[0], 0.2719384656089538);
+    chk!(d[1], 0.06952046368876559);
+
+    assert_eq!(g.density(f64::NAN), Err(InvalidData));
+    assert_eq!(g.densities(&[1.0, f64::INFINITY]), Err(InvalidData));
+}
+
+#[test]
+fn test_density_grid() {
+    let g = Kde::new(&ASCENDING, Kernel::Gaussian, Bandwidth::Fixed(1.0)).unwrap();
+    let (xs, ds) = g.grid(5).unwrap();
+    assert_eq!(xs, [-2.0, 0.5, 3.0, 5.5, 8.0]);
+    chk!(ds[0], 0.0009134342884470394);
+    chk!(ds[1], 0.09999997787198248);
+    chk!(ds[2], 0.1981731324932191);
+    chk!(ds[3], 0.09999997787198248);
+    chk!(ds[4], 0.0009134342884470394);
+    let e = Kde::new(&ASCENDING, Kernel::Epanechnikov, Bandwidth::Fixed(1.0)).unwrap();
+    let (_, ds) = e.grid(5).unwrap();
+    chk!(ds[0], 0.0);
+    chk!(ds[1], 0.10062305898749054);
+    chk!(ds[2], 0.20124611797498107);
+    chk!(ds[3], 0.10062305898749054);
+    chk!(ds[4], 0.0);
+
+    // Both kernels integrate to one.
+    for kernel in [Kernel::Gaussian, Kernel::Epanechnikov] {
+        let k = Kde::new(&B, kernel, Bandwidth::Scott).unwrap();
+        let (xs, ds) = k.grid(2001).unwrap();
+        let area: f64 = ds.iter().sum::<f64>() * (xs[1] - xs[0]);
+        assert!(f64::abs(area - 1.0) < 1e-3);
+    }
+    assert_eq!(g.grid(1), Err(InvalidParameter));
+}
+
+#[test]
+fn test_kde_errors() {
+    assert_eq!(
+        Kde::new(&[], Kernel::Gaussian, Bandwidth::Fixed(1.0)),
+        Err(NotEnoughData)
+    );
+    assert_eq!(
+        Kde::new(&[1.0], Kernel::Gaussian, Bandwidth::Scott),
+        Err(NotEnoughData)
+    );
+    assert_eq!(
+        Kde::new(&[1.0, f64::NAN], Kernel::Gaussian, Bandwidth::Fixed(1.0)),
+        Err(InvalidData)
+    );
+    assert_eq!(
+        Kde::new(&B, Kernel::Gaussian, Bandwidth::Fixed(0.0)),
+        Err(InvalidParameter)
+    );
+    assert_eq!(
+        Kde::new(&B, Kernel::Gaussian, Bandwidth::Fixed(-1.0)),
+        Err(InvalidParameter)
+    );
+    assert_eq!(
+        Kde::new(&B, Kernel::Gaussian, Bandwidth::Fixed(f64::NAN)),
+        Err(InvalidParameter)
+    );
+    assert_eq!(
+        Kde::new(&ONES, Kernel::Epanechnikov, Bandwidth::Silverman),
+        Err(Undefined)
+    );
+    // A single value works with a fixed bandwidth.
+    let k = Kde::new(&[0.0], Kernel::Gaussian, Bandwidth::Fixed(1.0)).unwrap();
+    chk!(k.density(0.0), Ok(0.3989422804014327));
+}
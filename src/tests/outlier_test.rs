@@ -0,0 +1,171 @@
+use crate::chk;
+use crate::error::StatsError::{InvalidData, InvalidParameter, NotEnoughData};
+use crate::hist::Histogram;
+use crate::incr::{Moments, Stats};
+use crate::outlier::{Detector, Method, Timing, Verdict};
+
+static ASCENDING: [f64; 5] = [1.0, 2.0, 3.0, 4.0, 5.0];
+
+// Values spread evenly through the buckets of a histogram from 0 to 10, so that its median is 5,
+// its quartiles are 2.5 and 7.5, and its MAD is 2.5.
+fn uniform() -> Stats {
+    let mut stats = Stats::with_histogram(Histogram::fixed(0.0, 10.0, 10).unwrap());
+    for i in 0..10 {
+        stats.update(i as f64 + 0.5).unwrap();
+    }
+    stats
+}
+
+fn ascending() -> Stats {
+    let mut stats = Stats::new();
+    stats.array_update(&ASCENDING).unwrap();
+    stats
+}
+
+#[test]
+fn test_z_score_detector() {
+    let mut d = Detector::new(ascending(), Method::ZScore(3.0), Timing::Before, false).unwrap();
+    chk!(d.score(5.0), Ok(1.2649110640673518));
+    assert_eq!(d.update(4.5), Ok(Verdict::Inlier));
+    assert_eq!(d.update(-3.0), Ok(Verdict::Outlier));
+    assert_eq!(d.stats().count(), 7);
+    assert_eq!(d.outliers(), 1);
+
+    // An excluded outlier doesn't update the stats.
+    let mut d = Detector::new(ascending(), Method::ZScore(3.0), Timing::Before, true).unwrap();
+    assert_eq!(d.update(10.0), Ok(Verdict::Outlier));
+    assert_eq!(d.update(10.0), Ok(Verdict::Outlier));
+    assert_eq!(d.stats().count(), 5);
+    chk!(d.stats().mean(), Ok(3.0));
+    assert_eq!(d.outliers(), 2);
+
+    // Judged after the update, the outlier inflates the standard deviation enough to mask itself.
+    let mut d = Detector::new(ascending(), Method::ZScore(3.0), Timing::After, false).unwrap();
+    assert_eq!(d.update(10.0), Ok(Verdict::Inlier));
+    assert_eq!(d.stats().count(), 6);
+    let mut d = Detector::new(ascending(), Method::ZScore(1.5), Timing::After, true).unwrap();
+    chk!(d.score(10.0), Ok(4.427188724235731));
+    assert_eq!(d.update(10.0), Ok(Verdict::Outlier));
+    assert_eq!(d.stats().count(), 5);
+    assert_eq!(d.update(4.0), Ok(Verdict::Inlier));
+    assert_eq!(d.into_stats().count(), 6);
+}
+
+#[test]
+fn test_undetermined() {
+    // Until the stats have some spread, values can't be judged, so they are all kept.
+    let mut d = Detector::new(Stats::new(), Method::ZScore(3.0), Timing::Before, true).unwrap();
+    assert_eq!(d.score(1.0), Err(NotEnoughData));
+    assert_eq!(d.update(1.0), Ok(Verdict::Undetermined));
+    assert_eq!(d.update(1.0), Ok(Verdict::Undetermined));
+    assert_eq!(d.update(100.0), Ok(Verdict::Undetermined));
+    assert_eq!(d.stats().count(), 3);
+    assert_eq!(d.outliers(), 0);
+
+    let stats = Stats::with_histogram(Histogram::fixed(0.0, 10.0, 10).unwrap());
+    let mut d = Detector::new(stats, Method::Tukey(1.5), Timing::Before, true).unwrap();
+    assert_eq!(d.update(1.0), Ok(Verdict::Undetermined));
+    assert_eq!(d.update(9.0), Ok(Verdict::Undetermined));
+    assert_eq!(d.update(5.0), Ok(Verdict::Inlier));
+}
+
+#[test]
+fn test_modified_z_score_detector() {
+    let mut d =
+        Detector::new(uniform(), Method::ModifiedZScore(3.5), Timing::Before, true).unwrap();
+    chk!(d.score(5.0), Ok(0.0));
+    chk!(d.score(20.0), Ok(4.047));
+    chk!(d.score(-10.0), Ok(-4.047));
+    assert_eq!(d.update(15.0), Ok(Verdict::Inlier));
+    assert_eq!(d.update(-25.0), Ok(Verdict::Outlier));
+    assert_eq!(d.stats().count(), 11);
+}
+
+#[test]
+fn test_tukey_detector() {
+    let mut d = Detector::new(uniform(), Method::Tukey(1.5), Timing::Before, false).unwrap();
+    chk!(d.score(5.0), Ok(0.0));
+    chk!(d.score(7.5), Ok(0.0));
+    chk!(d.score(20.0), Ok(2.5));
+    chk!(d.score(-5.0), Ok(-1.5));
+    assert_eq!(d.update(14.0), Ok(Verdict::Inlier));
+    assert_eq!(d.update(-6.0), Ok(Verdict::Outlier));
+    assert_eq!(d.stats().count(), 12);
+    assert_eq!(d.stats().histogram().unwrap().count(), 12);
+}
+
+#[test]
+fn test_detector_errors() {
+    assert!(matches!(
+        Detector::new(Stats::new(), Method::ZScore(0.0), Timing::Before, false),
+        Err(InvalidParameter)
+    ));
+    assert!(matches!(
+        Detector::new(
+            Stats::new(),
+            Method::ZScore(f64::NAN),
+            Timing::Before,
+            false
+        ),
+        Err(InvalidParameter)
+    ));
+    assert!(matches!(
+        Detector::new(Stats::new(), Method::Tukey(1.5), Timing::Before, false),
+        Err(InvalidParameter)
+    ));
+    assert!(matches!(
+        Detector::new(
+            Stats::new(),
+            Method::ModifiedZScore(3.5),
+            Timing::After,
+            false
+        ),
+        Err(InvalidParameter)
+    ));
+
+    assert!(matches!(
+        Detector::new(
+            Stats::with_moments(Moments::Mean),
+            Method::ZScore(3.0),
+            Timing::Before,
+            false
+        ),
+        Err(InvalidParameter)
+    ));
+
+    let mut d = Detector::new(ascending(), Method::ZScore(3.0), Timing::After, true).unwrap();
+    assert_eq!(d.update(f64::NAN), Err(InvalidData));
+    assert_eq!(d.score(f64::INFINITY), Err(InvalidData));
+    assert_eq!(d.stats().count(), 5);
+}
+
+#[test]
+fn test_excluded_after_update() {
+    // Values excluded after the update are scored as if they had been added, without adding them.
+    for (x, threshold) in [(10.0, 1.8), (10.0, 1.9), (-2.0, 1.5), (3.0, 0.1)] {
+        let mut updated = ascending();
+        updated.update(x).unwrap();
+        let expected = (x - updated.mean().unwrap()) / updated.sample_standard_deviation().unwrap();
+        let mut d =
+            Detector::new(ascending(), Method::ZScore(threshold), Timing::After, true).unwrap();
+        let verdict = d.update(x).unwrap();
+        if f64::abs(expected) > threshold {
+            assert_eq!(verdict, Verdict::Outlier);
+            assert_eq!(d.stats().count(), 5);
+        } else {
+            assert_eq!(verdict, Verdict::Inlier);
+            assert_eq!(d.stats().count(), 6);
+        }
+    }
+
+    // An excluded outlier leaves the histogram as it was.
+    let mut d = Detector::new(uniform(), Method::Tukey(1.5), Timing::After, true).unwrap();
+    assert_eq!(d.update(-20.0), Ok(Verdict::Outlier));
+    let histogram = d.stats().histogram().unwrap();
+    assert_eq!(histogram.count(), 10);
+    assert_eq!(histogram.underflow(), 0);
+    chk!(histogram.min(), Ok(0.5));
+    assert_eq!(d.update(4.0), Ok(Verdict::Inlier));
+    assert_eq!(d.stats().histogram().unwrap().count(), 11);
+    assert_eq!(d.stats().count(), 11);
+}
@@ -0,0 +1,50 @@
+use crate::batch;
+use crate::chk;
+use crate::error::StatsError::InvalidData;
+use crate::incr::Stats;
+use crate::vec;
+
+// Enough skewed data to be calculated in parallel.
+fn data() -> Vec<f64> {
+    (0..300_000)
+        .map(|i| f64::from(i % 1000).powi(2) / 1000.0 + 0.001 * f64::from(i % 7))
+        .collect()
+}
+
+// The parallel results match the serial incremental stats.
+#[test]
+fn test_parallel_batch() {
+    let a = data();
+    let s: Stats = a.iter().collect();
+    chk!(batch::min(&a), s.min());
+    chk!(batch::max(&a), s.max());
+    chk!(batch::sum(&a), s.sum());
+    chk!(batch::mean(&a), s.mean());
+    chk!(batch::population_variance(&a), s.population_variance());
+    chk!(batch::sample_variance(&a), s.sample_variance());
+    chk!(
+        batch::sample_standard_deviation(&a),
+        s.sample_standard_deviation()
+    );
+    chk!(batch::population_skewness(&a), s.population_skewness());
+    chk!(batch::sample_skewness(&a), s.sample_skewness());
+    chk!(batch::population_kurtosis(&a), s.population_kurtosis());
+    chk!(batch::sample_kurtosis(&a), s.sample_kurtosis());
+}
+
+#[test]
+fn test_parallel_descriptive() {
+    let a = data();
+    let s: Stats = a.iter().collect();
+    let d = vec::descriptive(&a).unwrap();
+    chk!(d.mean(), s.mean());
+    chk!(d.sample_variance(), s.sample_variance());
+    chk!(d.sample_skewness(), s.sample_skewness());
+    chk!(d.sample_kurtosis(), s.sample_kurtosis());
+
+    let mut b = a.clone();
+    b[200_000] = f64::NAN;
+    assert_eq!(batch::validate(&a), Ok(()));
+    assert_eq!(batch::validate(&b), Err(InvalidData));
+    assert!(matches!(vec::descriptive(&b), Err(InvalidData)));
+}
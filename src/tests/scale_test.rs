@@ -0,0 +1,108 @@
+use crate::chk;
+use crate::error::StatsError::{InvalidParameter, NotEnoughData, Undefined};
+use crate::incr;
+use crate::scale::Scaler;
+use crate::vec;
+
+static ONES: [f64; 10] = [1.0; 10];
+static ASCENDING: [f64; 5] = [1.0, 2.0, 3.0, 4.0, 5.0];
+static VALUES: [f64; 10] = [
+    1.0, -2.0, 13.0, 47.0, 115.0, -0.03, -123.4, 23.0, -23.04, 12.3,
+];
+
+fn check_all(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        chk!(*a, *e);
+    }
+}
+
+#[test]
+fn test_z_score_scaler() {
+    let mut d = incr::Stats::new();
+    d.array_update(&ASCENDING).unwrap();
+    let s = d.z_score_scaler().unwrap();
+    chk!(s.center(), 3.0);
+    chk!(s.scale(), 1.5811388300841898);
+    let z = s.transform(&ASCENDING);
+    check_all(
+        &z,
+        &[
+            -1.2649110640673518,
+            -0.6324555320336759,
+            0.0,
+            0.6324555320336759,
+            1.2649110640673518,
+        ],
+    );
+    check_all(&s.inverse_transform(&z), &ASCENDING);
+
+    let v = vec::Stats::new(&ASCENDING).unwrap();
+    assert_eq!(v.z_score_scaler(), Ok(s));
+
+    let mut data = ASCENDING;
+    s.transform_in_place(&mut data);
+    check_all(&data, &z);
+    s.inverse_transform_in_place(&mut data);
+    check_all(&data, &ASCENDING);
+}
+
+#[test]
+fn test_min_max_scaler() {
+    let mut d = incr::Stats::new();
+    d.array_update(&VALUES).unwrap();
+    let s = d.min_max_scaler().unwrap();
+    chk!(s.center(), -123.4);
+    chk!(s.scale(), 238.4);
+    let scaled = s.transform(&VALUES);
+    chk!(scaled[6], 0.0);
+    chk!(scaled[4], 1.0);
+    chk!(scaled[0], 124.4 / 238.4);
+    check_all(
+        &s.inverse_transform(&[0.0, 0.5, 1.0]),
+        &[-123.4, -4.2, 115.0],
+    );
+
+    let v = vec::Stats::new(&VALUES).unwrap();
+    assert_eq!(v.min_max_scaler(), Ok(s));
+}
+
+#[test]
+fn test_robust_scaler() {
+    let v = vec::Stats::new(&VALUES).unwrap();
+    let s = v.robust_scaler().unwrap();
+    chk!(s.center(), 6.65);
+    chk!(s.scale(), 22.0075);
+    let scaled = s.transform(&[6.65, 28.6575, -15.3575]);
+    check_all(&scaled, &[0.0, 1.0, -1.0]);
+
+    let a = vec::Stats::new(&ASCENDING).unwrap();
+    let s = a.robust_scaler().unwrap();
+    check_all(&s.transform(&ASCENDING), &[-1.0, -0.5, 0.0, 0.5, 1.0]);
+}
+
+#[test]
+fn test_scaler_errors() {
+    let s = Scaler::new(10.0, 2.0).unwrap();
+    check_all(&s.transform(&[8.0, 10.0, 13.0]), &[-1.0, 0.0, 1.5]);
+    assert_eq!(Scaler::new(1.0, 0.0), Err(InvalidParameter));
+    assert_eq!(Scaler::new(f64::NAN, 1.0), Err(InvalidParameter));
+    assert_eq!(Scaler::new(1.0, f64::INFINITY), Err(InvalidParameter));
+
+    // Data without any spread can't be scaled.
+    let mut d = incr::Stats::new();
+    assert_eq!(d.z_score_scaler(), Err(NotEnoughData));
+    assert_eq!(d.min_max_scaler(), Err(NotEnoughData));
+    d.update(1.0).unwrap();
+    assert_eq!(d.z_score_scaler(), Err(NotEnoughData));
+    assert_eq!(d.min_max_scaler(), Err(Undefined));
+    d.array_update(&ONES).unwrap();
+    assert_eq!(d.z_score_scaler(), Err(Undefined));
+
+    let v = vec::Stats::new(&ONES).unwrap();
+    assert_eq!(v.z_score_scaler(), Err(Undefined));
+    assert_eq!(v.min_max_scaler(), Err(Undefined));
+    assert_eq!(v.robust_scaler(), Err(Undefined));
+    let e = vec::Stats::new(&[]).unwrap();
+    assert_eq!(e.robust_scaler(), Err(NotEnoughData));
+}
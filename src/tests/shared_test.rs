@@ -0,0 +1,89 @@
+use std::thread;
+
+use crate::chk;
+use crate::error::StatsError::{InvalidData, InvalidParameter};
+use crate::hist::Histogram;
+use crate::incr::{Moments, Stats};
+use crate::shared::SharedStats;
+
+static VALUES: [f64; 10] = [
+    1.0, -2.0, 13.0, 47.0, 115.0, -0.03, -123.4, 23.0, -23.04, 12.3,
+];
+
+#[test]
+fn test_shared_stats() {
+    // Threads update the shared stats concurrently, each with a scaled copy of the values.
+    let shared = SharedStats::with_shards(Stats::new(), 4).unwrap();
+    assert_eq!(shared.shards(), 4);
+    let mut expected = Stats::new();
+    thread::scope(|s| {
+        for t in 0..8 {
+            let shared = &shared;
+            s.spawn(move || {
+                for _ in 0..100 {
+                    for x in VALUES {
+                        shared.update(x * t as f64).unwrap();
+                    }
+                }
+                shared.update_many(&VALUES.map(|x| x + t as f64)).unwrap();
+            });
+        }
+    });
+    for t in 0..8 {
+        for _ in 0..100 {
+            expected
+                .array_update(&VALUES.map(|x| x * t as f64))
+                .unwrap();
+        }
+        expected
+            .array_update(&VALUES.map(|x| x + t as f64))
+            .unwrap();
+    }
+
+    let stats = shared.snapshot();
+    assert_eq!(stats.count(), 8080);
+    chk!(stats.min(), expected.min());
+    chk!(stats.max(), expected.max());
+    chk!(stats.sum(), expected.sum());
+    chk!(stats.mean(), expected.mean());
+    chk!(stats.sample_variance(), expected.sample_variance());
+    chk!(stats.sample_skewness(), expected.sample_skewness());
+    chk!(stats.sample_kurtosis(), expected.sample_kurtosis());
+}
+
+#[test]
+fn test_shared_stats_config() {
+    let shared = SharedStats::new();
+    assert!(shared.shards() >= 1);
+    assert_eq!(shared.snapshot().count(), 0);
+    assert_eq!(shared.update(f64::NAN), Err(InvalidData));
+    assert_eq!(shared.update_many(&[1.0, f64::INFINITY]), Err(InvalidData));
+    assert_eq!(shared.snapshot().count(), 0);
+
+    // The shards copy the histogram and moments of the given stats.
+    let histogram = Histogram::fixed(-150.0, 150.0, 6).unwrap();
+    let shared = SharedStats::with_shards(Stats::with_histogram(histogram), 3).unwrap();
+    thread::scope(|s| {
+        for _ in 0..3 {
+            s.spawn(|| shared.update_many(&VALUES).unwrap());
+        }
+    });
+    let stats = shared.snapshot();
+    assert_eq!(stats.count(), 30);
+    assert_eq!(stats.histogram().unwrap().counts(), &[3, 0, 9, 15, 0, 3]);
+
+    let shared = SharedStats::with_shards(Stats::with_moments(Moments::Mean), 2).unwrap();
+    shared.update_many(&VALUES).unwrap();
+    assert_eq!(shared.snapshot().moments(), Moments::Mean);
+
+    assert!(matches!(
+        SharedStats::with_shards(Stats::new(), 0),
+        Err(InvalidParameter)
+    ));
+    let mut stats = Stats::new();
+    stats.update(1.0).unwrap();
+    assert!(matches!(
+        SharedStats::with_shards(stats, 2),
+        Err(InvalidParameter)
+    ));
+}
@@ -0,0 +1,160 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::{poll, stream, SinkExt, StreamExt};
+
+use crate::chk;
+use crate::error::StatsError::{InvalidData, InvalidParameter};
+#[cfg(feature = "alloc")]
+use crate::hist::Histogram;
+use crate::incr::Stats;
+use crate::stream::{StatsSink, SummariesExt};
+#[cfg(not(feature = "std"))]
+use std::vec::Vec;
+
+static VALUES: [f64; 10] = [
+    1.0, -2.0, 13.0, 47.0, 115.0, -0.03, -123.4, 23.0, -23.04, 12.3,
+];
+
+fn stats(data: &[f64]) -> Stats {
+    let mut stats = Stats::new();
+    stats.array_update(data).unwrap();
+    stats
+}
+
+#[test]
+fn test_summaries() {
+    let summaries: Vec<_> = block_on(
+        stream::iter(VALUES)
+            .summaries(Stats::new(), 4)
+            .unwrap()
+            .collect(),
+    );
+    assert_eq!(summaries.len(), 3);
+    for (summary, chunk) in summaries.iter().zip(VALUES.chunks(4)) {
+        let summary = summary.as_ref().unwrap();
+        let expected = stats(chunk);
+        assert_eq!(summary.count(), expected.count());
+        chk!(summary.mean(), expected.mean());
+        chk!(summary.sample_variance(), expected.sample_variance());
+    }
+
+    // A bad value is reported, and the summary continues without it.
+    let values = stream::iter([1.0, 2.0, f64::NAN, 3.0, 4.0]);
+    let summaries: Vec<_> = block_on(values.summaries(Stats::new(), 2).unwrap().collect());
+    assert_eq!(summaries.len(), 3);
+    chk!(summaries[0].as_ref().unwrap().mean(), Ok(1.5));
+    assert!(matches!(summaries[1], Err(InvalidData)));
+    chk!(summaries[2].as_ref().unwrap().mean(), Ok(3.5));
+
+    assert!(block_on(stream::empty().summaries(Stats::new(), 2).unwrap().next()).is_none());
+    assert!(matches!(
+        stream::iter(VALUES).summaries(Stats::new(), 0),
+        Err(InvalidParameter)
+    ));
+    assert!(matches!(
+        stream::iter(VALUES).summaries(stats(&VALUES), 2),
+        Err(InvalidParameter)
+    ));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_summaries_with_histogram() {
+    let histogram = Histogram::fixed(-150.0, 150.0, 6).unwrap();
+    let mut summaries = stream::iter(VALUES)
+        .summaries(Stats::with_histogram(histogram), 100)
+        .unwrap();
+    let summary = block_on(summaries.next()).unwrap().unwrap();
+    assert_eq!(summary.histogram().unwrap().counts(), &[1, 0, 3, 5, 0, 1]);
+    assert!(block_on(summaries.next()).is_none());
+}
+
+#[test]
+fn test_summaries_on_ticks() {
+    let (mut values_tx, values_rx) = mpsc::unbounded();
+    let (mut ticks_tx, ticks_rx) = mpsc::unbounded();
+    let mut summaries = values_rx.summaries_on(Stats::new(), ticks_rx).unwrap();
+    block_on(async {
+        for x in &VALUES[..3] {
+            values_tx.send(*x).await.unwrap();
+        }
+        // The values are taken while waiting for a tick.
+        assert!(poll!(summaries.next()).is_pending());
+        ticks_tx.send(()).await.unwrap();
+        let summary = summaries.next().await.unwrap().unwrap();
+        assert_eq!(summary.count(), 3);
+        chk!(summary.sum(), Ok(12.0));
+
+        // An interval without values is summarized too.
+        ticks_tx.send(()).await.unwrap();
+        assert_eq!(summaries.next().await.unwrap().unwrap().count(), 0);
+
+        // When the values end, the rest are summarized.
+        for x in &VALUES[3..] {
+            values_tx.send(*x).await.unwrap();
+        }
+        values_tx.close_channel();
+        let summary = summaries.next().await.unwrap().unwrap();
+        assert_eq!(summary.count(), 7);
+        chk!(summary.mean(), stats(&VALUES[3..]).mean());
+        assert!(summaries.next().await.is_none());
+    });
+}
+
+#[test]
+fn test_stats_sink() {
+    let mut sink = StatsSink::new(Stats::new()).unwrap();
+    block_on(stream::iter(VALUES).map(Ok).forward(&mut sink)).unwrap();
+    assert_eq!(sink.stats().count(), 10);
+    chk!(
+        sink.stats().sample_kurtosis(),
+        stats(&VALUES).sample_kurtosis()
+    );
+
+    let taken = sink.take();
+    assert_eq!(taken.count(), 10);
+    assert_eq!(sink.stats().count(), 0);
+    assert_eq!(block_on(sink.send(f64::NAN)), Err(InvalidData));
+    block_on(sink.send(2.0)).unwrap();
+    chk!(sink.into_stats().mean(), Ok(2.0));
+
+    assert!(matches!(
+        StatsSink::new(stats(&VALUES)),
+        Err(InvalidParameter)
+    ));
+}
+
+#[test]
+fn test_summaries_of_ready_values() {
+    // Values that are always ready don't hold back the ticks.
+    let values = stream::repeat(1.0).take(100000);
+    let summaries: Vec<_> = block_on(
+        values
+            .summaries_on(Stats::new(), stream::repeat(()))
+            .unwrap()
+            .collect(),
+    );
+    assert!(summaries.len() > 1);
+    let count: u32 = summaries.iter().map(|s| s.as_ref().unwrap().count()).sum();
+    assert_eq!(count, 100000);
+
+    // A poll takes a limited number of values before yielding to the executor.
+    let mut summaries = stream::repeat(1.0)
+        .summaries_on(Stats::new(), stream::pending::<()>())
+        .unwrap();
+    assert!(block_on(async { poll!(summaries.next()) }).is_pending());
+    let mut summaries = stream::repeat(1.0)
+        .summaries(Stats::new(), u32::MAX)
+        .unwrap();
+    assert!(block_on(async { poll!(summaries.next()) }).is_pending());
+
+    let values = stream::repeat(2.0).take(5000);
+    let summaries: Vec<_> = block_on(
+        values
+            .summaries_on(Stats::new(), stream::pending::<()>())
+            .unwrap()
+            .collect(),
+    );
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].as_ref().unwrap().count(), 5000);
+}
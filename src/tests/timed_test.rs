@@ -0,0 +1,102 @@
+use crate::chk;
+#[cfg(feature = "alloc")]
+use crate::error::StatsError::InvalidParameter;
+use crate::error::StatsError::{InvalidData, NotEnoughData};
+use crate::timed::TimeWeighted;
+#[cfg(feature = "alloc")]
+use crate::timed::TimeWindow;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use std::vec::Vec;
+
+// A gauge sampled irregularly, as (seconds, value).
+static SAMPLES: [(f64, f64); 4] = [(0.0, 1.0), (1.0, 3.0), (4.0, 2.0), (5.0, 10.0)];
+
+#[test]
+fn test_time_weighted() {
+    let mut s = TimeWeighted::new();
+    for (t, x) in SAMPLES {
+        s.update(t, x).unwrap();
+    }
+    assert_eq!(s.stats().count(), 4);
+    chk!(s.stats().mean(), Ok(4.0));
+    chk!(s.duration(), Ok(5.0));
+    chk!(s.time_weighted_mean(), Ok(2.4));
+    chk!(s.time_weighted_variance(), Ok(0.64));
+    chk!(s.time_weighted_standard_deviation(), Ok(0.8));
+    chk!(s.rate(), Ok(1.8));
+
+    // The last value holds until the stats are advanced, which doesn't change the rate.
+    s.advance(10.0).unwrap();
+    chk!(s.duration(), Ok(10.0));
+    chk!(s.time_weighted_mean(), Ok(6.2));
+    chk!(s.time_weighted_variance(), Ok(14.76));
+    chk!(s.rate(), Ok(1.8));
+    assert_eq!(s.stats().count(), 4);
+}
+
+#[test]
+fn test_time_weighted_errors() {
+    let mut s = TimeWeighted::new();
+    assert_eq!(s.duration(), Err(NotEnoughData));
+    assert_eq!(s.advance(1.0), Err(NotEnoughData));
+    assert_eq!(s.update(f64::NAN, 1.0), Err(InvalidData));
+    s.update(2.0, 1.0).unwrap();
+    assert_eq!(s.time_weighted_mean(), Err(NotEnoughData));
+    assert_eq!(s.rate(), Err(NotEnoughData));
+
+    // Times can't go backwards, and the stats are unchanged by bad samples.
+    assert_eq!(s.update(1.0, 1.0), Err(InvalidData));
+    assert_eq!(s.update(3.0, f64::INFINITY), Err(InvalidData));
+    assert_eq!(s.advance(f64::INFINITY), Err(InvalidData));
+    assert_eq!(s.stats().count(), 1);
+    chk!(s.duration(), Ok(0.0));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_time_window() {
+    let mut w = TimeWindow::new(3.0).unwrap();
+    w.update(0.0, 1.0).unwrap();
+    assert_eq!(w.time_weighted_mean(), Err(NotEnoughData));
+    w.update(1.0, 3.0).unwrap();
+    chk!(w.time_weighted_mean(), Ok(1.0));
+    chk!(w.rate(), Ok(2.0));
+
+    // The first samples leave the window, but the value of the latest of them holds at its start.
+    w.update(4.0, 2.0).unwrap();
+    w.update(5.0, 10.0).unwrap();
+    assert_eq!(w.count(), 2);
+    assert_eq!(
+        w.samples().collect::<Vec<_>>(),
+        vec![(4.0, 2.0), (5.0, 10.0)]
+    );
+    chk!(w.stats().mean(), Ok(6.0));
+    chk!(w.time_weighted_mean(), Ok(8.0 / 3.0));
+    chk!(w.rate(), Ok(8.0));
+
+    w.advance(6.5).unwrap();
+    assert_eq!(w.count(), 2);
+    chk!(w.time_weighted_mean(), Ok(18.5 / 3.0));
+
+    // An empty window still has the value in effect.
+    w.advance(20.0).unwrap();
+    assert_eq!(w.count(), 0);
+    assert_eq!(w.stats().mean(), Err(NotEnoughData));
+    chk!(w.time_weighted_mean(), Ok(10.0));
+    assert_eq!(w.rate(), Err(NotEnoughData));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_time_window_errors() {
+    assert!(matches!(TimeWindow::new(0.0), Err(InvalidParameter)));
+    assert!(matches!(TimeWindow::new(f64::NAN), Err(InvalidParameter)));
+
+    let mut w = TimeWindow::new(60.0).unwrap();
+    assert_eq!(w.time_weighted_mean(), Err(NotEnoughData));
+    w.advance(5.0).unwrap();
+    assert_eq!(w.time_weighted_mean(), Err(NotEnoughData));
+    assert_eq!(w.update(4.0, 1.0), Err(InvalidData));
+    assert_eq!(w.update(6.0, f64::NAN), Err(InvalidData));
+    assert_eq!(w.count(), 0);
+}
@@ -1,6 +1,7 @@
 use crate::chk;
-use crate::error::StatsError::{InvalidData, NotEnoughData, Undefined};
-use crate::vec::Stats;
+use crate::error::StatsError::{InvalidData, InvalidParameter, NotEnoughData, Undefined};
+use crate::hist::{BinRule, Bins};
+use crate::vec::{self, Stats};
 
 // Test the incremtal functions. Update the descriptive stats one point at a time.
 static ZEROS: [f64; 10] = [0.0; 10];
@@ -315,3 +316,252 @@ fn test_update10() {
     chk!(d.population_kurtosis(), Ok(1.253240236214162));
     chk!(d.sample_kurtosis(), Ok(3.179835417592894));
 }
+
+#[test]
+// Confidence intervals for the mean (Student's t) and the variance and standard deviation
+// (chi-squared).
+fn test_confidence_intervals() {
+    let d = Stats::new(&VALUES).unwrap();
+    let (lower, upper) = d.mean_confidence_interval(0.95).unwrap();
+    chk!(lower, -36.14002459334809);
+    chk!(upper, 48.70602459334808);
+    let (lower, upper) = d.variance_confidence_interval(0.95).unwrap();
+    chk!(lower, 1663.8972806186584);
+    chk!(upper, 11721.24673504701);
+    let (lower, upper) = d.standard_deviation_confidence_interval(0.95).unwrap();
+    chk!(lower, 40.79089703130661);
+    chk!(upper, 108.26470678409936);
+    let (lower, upper) = d.mean_confidence_interval(0.9).unwrap();
+    chk!(lower, -28.094008062634362);
+    chk!(upper, 40.66000806263436);
+    let (lower, upper) = d.standard_deviation_confidence_interval(0.9).unwrap();
+    chk!(lower, 43.25268292812368);
+    chk!(upper, 97.56564391412302);
+
+    let d = Stats::new(&ASCENDING).unwrap();
+    let (lower, upper) = d.mean_confidence_interval(0.99).unwrap();
+    chk!(lower, -0.25558670475778533);
+    chk!(upper, 6.2555867047577856);
+    let (lower, upper) = d.variance_confidence_interval(0.99).unwrap();
+    chk!(lower, 0.6729357812419683);
+    chk!(upper, 48.31172421258249);
+
+    // The level must be a probability strictly between 0 and 1.
+    assert_eq!(d.mean_confidence_interval(0.0), Err(InvalidParameter));
+    assert_eq!(d.mean_confidence_interval(1.0), Err(InvalidParameter));
+    assert_eq!(d.variance_confidence_interval(-0.5), Err(InvalidParameter));
+    assert_eq!(
+        d.standard_deviation_confidence_interval(f64::NAN),
+        Err(InvalidParameter)
+    );
+
+    // At least two values are needed to estimate the spread.
+    let d = Stats::new(&ONES[..1]).unwrap();
+    assert_eq!(d.mean_confidence_interval(0.95), Err(NotEnoughData));
+    assert_eq!(d.variance_confidence_interval(0.95), Err(NotEnoughData));
+    assert_eq!(
+        d.standard_deviation_confidence_interval(0.95),
+        Err(NotEnoughData)
+    );
+}
+
+#[test]
+// The Jarque-Bera and D'Agostino K-squared normality tests.
+fn test_normality_tests() {
+    let d = Stats::new(&VALUES).unwrap();
+    let r = d.jarque_bera().unwrap();
+    chk!(r.statistic, 1.0336992860361678);
+    chk!(r.p_value, 0.5963964532635635);
+    let r = d.dagostino_k_squared().unwrap();
+    chk!(r.statistic, 4.423738209435052);
+    chk!(r.p_value, 0.1094957980190081);
+
+    // D'Agostino's transformations require at least 8 values.
+    let d = Stats::new(&VALUES[..7]).unwrap();
+    assert!(d.jarque_bera().is_ok());
+    assert_eq!(d.dagostino_k_squared(), Err(NotEnoughData));
+
+    // The tests are undefined if all of the values are the same.
+    let d = Stats::new(&ONES).unwrap();
+    assert_eq!(d.jarque_bera(), Err(Undefined));
+    assert_eq!(d.dagostino_k_squared(), Err(Undefined));
+}
+
+#[test]
+// Two-sample t-tests and F-test, comparing VALUES with ASCENDING and ASCENDING with a third sample.
+fn test_two_sample_tests() {
+    let b_data = [2.1, 3.3, 1.9, 4.8, 2.6, 3.9, 5.2, 3.1];
+    let v = Stats::new(&VALUES).unwrap();
+    let a = Stats::new(&ASCENDING).unwrap();
+    let b = Stats::new(&b_data).unwrap();
+    let r = v.students_t_test(&a).unwrap();
+    chk!(r.statistic, 0.1214543931242461);
+    chk!(r.df, 13.0);
+    chk!(r.p_value, 0.9051884162750196);
+    let r = v.welch_t_test(&a).unwrap();
+    chk!(r.statistic, 0.17493774580825308);
+    chk!(r.df, 9.025567999662424);
+    chk!(r.p_value, 0.8649900493720019);
+    let r = v.f_test(&a).unwrap();
+    chk!(r.statistic, 1406.752516);
+    chk!(r.df_numerator, 9.0);
+    chk!(r.df_denominator, 4.0);
+    chk!(r.p_value, 2.467063413869685e-6);
+
+    let r = a.students_t_test(&b).unwrap();
+    chk!(r.statistic, -0.4701778610985646);
+    chk!(r.df, 11.0);
+    chk!(r.p_value, 0.6474121307543849);
+    let r = a.welch_t_test(&b).unwrap();
+    chk!(r.statistic, -0.43937232578122587);
+    chk!(r.df, 6.8986359843262415);
+    chk!(r.p_value, 0.6738251081465415);
+    let r = a.f_test(&b).unwrap();
+    chk!(r.statistic, 1.7294626312538603);
+    chk!(r.p_value, 0.4943891303962648);
+    // The two-sided p-value doesn't depend on the order of the samples.
+    let r = b.f_test(&a).unwrap();
+    chk!(r.statistic, 0.5782142857142857);
+    chk!(r.p_value, 0.4943891303962648);
+
+    // Each sample needs at least two values, and the tests are undefined without any variance.
+    let one = Stats::new(&ONES[..1]).unwrap();
+    let ones = Stats::new(&ONES).unwrap();
+    let more_ones = Stats::new(&ONES[..5]).unwrap();
+    assert_eq!(a.welch_t_test(&one), Err(NotEnoughData));
+    assert_eq!(one.students_t_test(&a), Err(NotEnoughData));
+    assert_eq!(a.f_test(&one), Err(NotEnoughData));
+    assert_eq!(ones.welch_t_test(&more_ones), Err(Undefined));
+    assert_eq!(ones.students_t_test(&more_ones), Err(Undefined));
+    assert_eq!(a.f_test(&ones), Err(Undefined));
+}
+
+#[test]
+fn test_range_and_histogram() {
+    let d = Stats::new(&VALUES).unwrap();
+    chk!(d.range(), Ok(238.4));
+    chk!(d.interquartile_range(), Ok(22.0075));
+    chk!(d.median(), Ok(6.65));
+    let a = Stats::new(&ASCENDING).unwrap();
+    chk!(a.interquartile_range(), Ok(2.0));
+    let e = Stats::new(&[]).unwrap();
+    assert_eq!(e.range(), Err(NotEnoughData));
+    assert_eq!(e.interquartile_range(), Err(NotEnoughData));
+
+    let (counts, edges) = d.histogram(Bins::Count(4)).unwrap();
+    assert_eq!(counts, [1, 1, 7, 1]);
+    chk!(edges[1], -63.800000000000004);
+    let (counts, _) = d
+        .histogram(Bins::Edges(&[-100.0, 0.0, 10.0, 20.0, 200.0]))
+        .unwrap();
+    assert_eq!(counts, [3, 1, 2, 3]);
+    let (counts, _) = d.histogram(Bins::Rule(BinRule::Auto)).unwrap();
+    assert_eq!(counts, [1, 0, 0, 0, 0, 1, 5, 1, 1, 0, 0, 1]);
+    let (counts, _) = d.histogram(Bins::Rule(BinRule::Sturges)).unwrap();
+    assert_eq!(counts, [1, 0, 6, 2, 1]);
+    let (counts, _) = d.histogram(Bins::Rule(BinRule::Scott)).unwrap();
+    assert_eq!(counts, [1, 7, 2]);
+    let (counts, _) = d.histogram(Bins::Rule(BinRule::Sqrt)).unwrap();
+    assert_eq!(counts, [1, 1, 7, 1]);
+    let (counts, _) = d.histogram(Bins::Rule(BinRule::Doane)).unwrap();
+    assert_eq!(counts, [1, 0, 1, 6, 1, 1]);
+
+    let ones = Stats::new(&ONES).unwrap();
+    let (counts, edges) = ones.histogram(Bins::Rule(BinRule::Scott)).unwrap();
+    assert_eq!(counts, [10]);
+    assert_eq!(edges, [0.5, 1.5]);
+    assert_eq!(e.histogram(Bins::Rule(BinRule::Auto)), Err(NotEnoughData));
+    assert_eq!(d.histogram(Bins::Count(0)), Err(InvalidParameter));
+    let huge = Stats::new(&[-1e308, 0.0, 1e308]).unwrap();
+    assert_eq!(huge.histogram(Bins::Count(3)), Err(Undefined));
+    assert_eq!(huge.histogram(Bins::Rule(BinRule::Sturges)), Err(Undefined));
+}
+
+#[test]
+fn test_descriptive() {
+    // The same as the stats calculated one at a time, whether or not the data fills the SIMD lanes.
+    for len in 4..=VALUES.len() {
+        let a = &VALUES[..len];
+        let d = vec::descriptive(a).unwrap();
+        let s = Stats::new(a).unwrap();
+        chk!(d.min(), s.min());
+        chk!(d.max(), s.max());
+        chk!(d.sum(), s.sum());
+        chk!(d.mean(), s.mean());
+        chk!(d.population_variance(), s.population_variance());
+        chk!(d.sample_variance(), s.sample_variance());
+        chk!(
+            d.population_standard_deviation(),
+            s.population_standard_deviation()
+        );
+        chk!(d.sample_standard_deviation(), s.sample_standard_deviation());
+        chk!(d.population_skewness(), s.population_skewness());
+        chk!(d.sample_skewness(), s.sample_skewness());
+        chk!(d.population_kurtosis(), s.population_kurtosis());
+        chk!(d.sample_kurtosis(), s.sample_kurtosis());
+    }
+
+    assert!(matches!(vec::descriptive(&[]), Err(NotEnoughData)));
+    assert!(matches!(
+        vec::descriptive(&ASCENDING[..3]),
+        Err(NotEnoughData)
+    ));
+    assert!(matches!(vec::descriptive(&ONES), Err(Undefined)));
+    assert!(matches!(
+        vec::descriptive(&[1.0, f64::NAN]),
+        Err(InvalidData)
+    ));
+}
+
+// Stats that own their data, which can outlive it and move to another thread.
+fn owned_stats() -> Stats<'static> {
+    let data = VALUES.to_vec();
+    Stats::from_vec(data).unwrap()
+}
+
+#[test]
+fn test_owned() {
+    let s = owned_stats();
+    let borrowed = Stats::new(&VALUES).unwrap();
+    assert_eq!(s.data(), &VALUES);
+    chk!(s.count(), 10);
+    chk!(s.median(), borrowed.median());
+    chk!(s.sample_variance(), borrowed.sample_variance());
+    chk!(s.sample_kurtosis(), borrowed.sample_kurtosis());
+
+    let kurtosis = std::thread::spawn(move || s.sample_kurtosis())
+        .join()
+        .unwrap();
+    chk!(kurtosis, borrowed.sample_kurtosis());
+
+    // The statistics calculated through the shared reference are kept when the data is copied.
+    let copied = {
+        let data = ASCENDING.to_vec();
+        let d = vec::descriptive(&data).unwrap();
+        d.into_owned()
+    };
+    assert_eq!(copied, vec::descriptive(&ASCENDING).unwrap());
+    chk!(copied.mean(), Ok(3.0));
+    chk!(copied.sample_variance(), Ok(2.5));
+
+    assert_eq!(Stats::from_vec(vec![1.0, f64::NAN]), Err(InvalidData));
+    chk!(Stats::from_vec(vec![]).unwrap().mean(), Err(NotEnoughData));
+}
+
+// With std, Stats can be sent to and shared between threads, which calculate and cache their
+// statistics through the shared reference.
+#[cfg(feature = "std")]
+#[test]
+fn test_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Stats<'static>>();
+    assert_send_sync::<Stats<'_>>();
+
+    let s = Stats::new(&VALUES).unwrap();
+    let expected = Stats::new(&VALUES).unwrap();
+    std::thread::scope(|scope| {
+        let kurtosis = scope.spawn(|| s.sample_kurtosis());
+        chk!(s.sample_skewness(), expected.sample_skewness());
+        chk!(kurtosis.join().unwrap(), expected.sample_kurtosis());
+    });
+}
@@ -0,0 +1,76 @@
+use crate::batch;
+use crate::chk;
+use crate::error::StatsError::{InvalidData, InvalidParameter, NotEnoughData};
+use crate::incr::Moments;
+use crate::window::Window;
+#[cfg(not(feature = "std"))]
+use std::vec::Vec;
+
+static VALUES: [f64; 10] = [
+    1.0, -2.0, 13.0, 47.0, 115.0, -0.03, -123.4, 23.0, -23.04, 12.3,
+];
+
+#[test]
+fn test_window() {
+    let mut w = Window::<4>::new();
+    assert_eq!(w.stats().mean(), Err(NotEnoughData));
+    assert!(!w.is_full());
+
+    // Each window's stats match the batch functions on its values, before and after it's full, and
+    // over several recalculations.
+    let data: Vec<f64> = (0..5).flat_map(|_| VALUES).collect();
+    for (i, &x) in data.iter().enumerate() {
+        w.update(x).unwrap();
+        let a = &data[(i + 1).saturating_sub(4)..=i];
+        assert_eq!(w.values().collect::<Vec<_>>(), a);
+        assert_eq!(w.is_full(), a.len() == 4);
+        chk!(w.stats().count(), a.len() as u32);
+        chk!(w.stats().min(), batch::min(a));
+        chk!(w.stats().max(), batch::max(a));
+        chk!(w.stats().sum(), batch::sum(a));
+        chk!(w.stats().mean(), batch::mean(a));
+        if a.len() > 1 {
+            chk!(w.stats().sample_variance(), batch::sample_variance(a));
+        }
+        if a.len() > 2 {
+            chk!(
+                w.stats().population_skewness(),
+                batch::population_skewness(a)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_window_of_equal_values() {
+    // Once the window holds only equal values, it has no variance.
+    let mut w = Window::<3>::new();
+    w.array_update(&[1.0, 1e8, -3.5, 0.1, 0.1, 0.1]).unwrap();
+    assert_eq!(w.stats().population_variance(), Ok(0.0));
+    assert_eq!(w.stats().min(), Ok(0.1));
+    assert_eq!(w.stats().max(), Ok(0.1));
+}
+
+#[test]
+fn test_window_with_moments() {
+    let mut w = Window::<3>::with_moments(Moments::Variance);
+    w.array_update(&VALUES).unwrap();
+    assert_eq!(w.stats().moments(), Moments::Variance);
+    chk!(w.stats().mean(), batch::mean(&VALUES[7..]));
+    chk!(
+        w.stats().sample_variance(),
+        batch::sample_variance(&VALUES[7..])
+    );
+    assert_eq!(w.stats().population_skewness(), Err(InvalidParameter));
+}
+
+#[test]
+fn test_window_errors() {
+    // A NaN or Inf is rejected, leaving the window unchanged.
+    let mut w = Window::<2>::new();
+    w.array_update(&[1.0, 2.0]).unwrap();
+    assert_eq!(w.update(f64::NAN), Err(InvalidData));
+    assert_eq!(w.update(f64::INFINITY), Err(InvalidData));
+    assert_eq!(w.values().collect::<Vec<_>>(), [1.0, 2.0]);
+    chk!(w.stats().mean(), Ok(1.5));
+}
@@ -0,0 +1,233 @@
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+
+use crate::error::{Result, StatsError};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::float::Float;
+use crate::incr::Stats;
+
+//
+// Time-aware statistics
+//
+// These take (time, value) samples, with the times in seconds and never decreasing. Irregularly
+// sampled gauges are weighted by how long each value was in effect: a value holds from its own time
+// until the next sample's, so time-weighted statistics integrate a step function. The unweighted
+// stats of the samples are available too.
+
+// Time-weighted stats of all of the samples.
+#[derive(Default, Clone)]
+pub struct TimeWeighted {
+    stats: Stats,
+    start: f64,
+    end: f64,
+    last_time: f64,
+    last_value: f64,
+    // The integrals of the values and their squares, shifted by the first value to limit
+    // cancellation in the variance.
+    shift: f64,
+    integral: f64,
+    integral_squares: f64,
+}
+
+impl TimeWeighted {
+    pub fn new() -> Self {
+        TimeWeighted {
+            ..Default::default()
+        }
+    }
+
+    // Update the stats with the value sampled at the given time.
+    pub fn update(&mut self, t: f64, x: f64) -> Result<()> {
+        if f64::is_nan(x) || f64::is_infinite(x) {
+            return Err(StatsError::InvalidData);
+        }
+        if self.stats.count() == 0 {
+            if !f64::is_finite(t) {
+                return Err(StatsError::InvalidData);
+            }
+            self.start = t;
+            self.end = t;
+            self.shift = x;
+        } else {
+            self.advance(t)?;
+        }
+        self.stats.update(x)?;
+        self.last_time = t;
+        self.last_value = x;
+        Ok(())
+    }
+
+    // Hold the last value until the given time, without a new sample.
+    pub fn advance(&mut self, t: f64) -> Result<()> {
+        if self.stats.count() == 0 {
+            return Err(StatsError::NotEnoughData);
+        }
+        if !f64::is_finite(t) || t < self.end {
+            return Err(StatsError::InvalidData);
+        }
+        let y = self.last_value - self.shift;
+        self.integral += y * (t - self.end);
+        self.integral_squares += y * y * (t - self.end);
+        self.end = t;
+        Ok(())
+    }
+
+    // The unweighted stats of the sampled values.
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    // The time from the first sample to the last sample or advance.
+    pub fn duration(&self) -> Result<f64> {
+        if self.stats.count() == 0 {
+            return Err(StatsError::NotEnoughData);
+        }
+        Ok(self.end - self.start)
+    }
+
+    // The mean of the values, each weighted by how long it was in effect.
+    pub fn time_weighted_mean(&self) -> Result<f64> {
+        Ok(self.shift + self.integral / self.weighted_duration()?)
+    }
+
+    // The variance of the values over time, which is a population variance.
+    pub fn time_weighted_variance(&self) -> Result<f64> {
+        let duration = self.weighted_duration()?;
+        let mean = self.integral / duration;
+        Ok(f64::max(
+            0.0,
+            self.integral_squares / duration - mean * mean,
+        ))
+    }
+
+    pub fn time_weighted_standard_deviation(&self) -> Result<f64> {
+        Ok(self.time_weighted_variance()?.sqrt())
+    }
+
+    // The change in the value per second, between the first and last samples.
+    pub fn rate(&self) -> Result<f64> {
+        if self.stats.count() < 2 || self.last_time == self.start {
+            return Err(StatsError::NotEnoughData);
+        }
+        Ok((self.last_value - self.shift) / (self.last_time - self.start))
+    }
+
+    fn weighted_duration(&self) -> Result<f64> {
+        let duration = self.duration()?;
+        if duration == 0.0 {
+            return Err(StatsError::NotEnoughData);
+        }
+        Ok(duration)
+    }
+}
+
+// Stats of the samples in a sliding window covering the last `width` seconds, up to the latest
+// sample or advance. The samples in the window are stored, and the stats are calculated from them
+// when requested.
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct TimeWindow {
+    width: f64,
+    now: Option<f64>,
+    samples: VecDeque<(f64, f64)>,
+    // The latest sample to have left the window, whose value is still in effect at its start.
+    expired: Option<(f64, f64)>,
+}
+
+#[cfg(feature = "alloc")]
+impl TimeWindow {
+    pub fn new(width: f64) -> Result<Self> {
+        if !(width > 0.0 && f64::is_finite(width)) {
+            return Err(StatsError::InvalidParameter);
+        }
+        Ok(TimeWindow {
+            width,
+            now: None,
+            samples: VecDeque::new(),
+            expired: None,
+        })
+    }
+
+    // Add the value sampled at the given time, dropping the samples that are now too old.
+    pub fn update(&mut self, t: f64, x: f64) -> Result<()> {
+        if f64::is_nan(x) || f64::is_infinite(x) {
+            return Err(StatsError::InvalidData);
+        }
+        self.advance(t)?;
+        self.samples.push_back((t, x));
+        Ok(())
+    }
+
+    // Move the end of the window to the given time, without a new sample.
+    pub fn advance(&mut self, t: f64) -> Result<()> {
+        if !f64::is_finite(t) || self.now.is_some_and(|now| t < now) {
+            return Err(StatsError::InvalidData);
+        }
+        self.now = Some(t);
+        while let Some(&(time, value)) = self.samples.front() {
+            if time >= t - self.width {
+                break;
+            }
+            self.expired = Some((time, value));
+            self.samples.pop_front();
+        }
+        Ok(())
+    }
+
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    // The number of samples in the window.
+    pub fn count(&self) -> u32 {
+        self.samples.len() as u32
+    }
+
+    // The samples in the window, oldest first.
+    pub fn samples(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.samples.iter().copied()
+    }
+
+    // The unweighted stats of the values in the window.
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats::new();
+        for (_, x) in &self.samples {
+            // The values were validated when they were added.
+            let _ = stats.update(*x);
+        }
+        stats
+    }
+
+    // The mean of the values over the window, each weighted by how long it was in effect. Until
+    // a sample has expired, the window starts at the first sample.
+    pub fn time_weighted_mean(&self) -> Result<f64> {
+        let now = self.now.ok_or(StatsError::NotEnoughData)?;
+        let (mut time, mut value) = match self.expired {
+            Some((_, value)) => (now - self.width, value),
+            None => *self.samples.front().ok_or(StatsError::NotEnoughData)?,
+        };
+        let start = time;
+        let mut integral = 0.0;
+        for &(t, x) in &self.samples {
+            integral += value * (t - time);
+            (time, value) = (t, x);
+        }
+        integral += value * (now - time);
+        if now == start {
+            return Err(StatsError::NotEnoughData);
+        }
+        Ok(integral / (now - start))
+    }
+
+    // The change in the value per second, between the first and last samples in the window.
+    pub fn rate(&self) -> Result<f64> {
+        let (Some(first), Some(last)) = (self.samples.front(), self.samples.back()) else {
+            return Err(StatsError::NotEnoughData);
+        };
+        if last.0 == first.0 {
+            return Err(StatsError::NotEnoughData);
+        }
+        Ok((last.1 - first.1) / (last.0 - first.0))
+    }
+}
@@ -0,0 +1,104 @@
+use core::mem;
+
+use crate::error::Result;
+use crate::incr::{Moments, Stats};
+
+//
+// Fixed-capacity windows
+//
+// A Window keeps the stats of the last N values in an array of N values, with N a const generic
+// parameter, so its size is fixed at compile time and it needs no allocator. As in the rolling
+// batch functions, each value is added to the moments as it enters the window and removed as it
+// leaves, and the moments are recalculated from the window's values every N values so that the
+// rounding errors don't accumulate. The min and max are recalculated when a value equal to one of
+// them leaves.
+
+#[derive(Clone)]
+pub struct Window<const N: usize> {
+    values: [f64; N],
+    start: usize, // The index of the oldest value.
+    len: usize,
+    stats: Stats,
+    removed: usize, // The values removed since the stats were last recalculated.
+}
+
+impl<const N: usize> Window<N> {
+    // Evaluated when a window is created, so that a Window<0> doesn't compile.
+    const NOT_EMPTY: () = assert!(N > 0, "a Window needs room for at least one value");
+
+    // Create an empty window.
+    pub fn new() -> Self {
+        Self::with_moments(Moments::Kurtosis)
+    }
+
+    // Create an empty window whose stats only maintain the moments up to the given one, as with
+    // Stats::with_moments().
+    pub fn with_moments(moments: Moments) -> Self {
+        let () = Self::NOT_EMPTY;
+        Window {
+            values: [0.0; N],
+            start: 0,
+            len: 0,
+            stats: Stats::with_moments(moments),
+            removed: 0,
+        }
+    }
+
+    // Add a value to the window, removing the oldest value once the window is full.
+    pub fn update(&mut self, x: f64) -> Result<()> {
+        self.stats.update(x)?;
+        if self.len < N {
+            self.values[(self.start + self.len) % N] = x;
+            self.len += 1;
+            return Ok(());
+        }
+        let oldest = mem::replace(&mut self.values[self.start], x);
+        self.start = (self.start + 1) % N;
+        self.removed += 1;
+        if self.removed == N || oldest == self.stats.min()? || oldest == self.stats.max()? {
+            self.recalculate()
+        } else {
+            self.stats.remove(oldest);
+            Ok(())
+        }
+    }
+
+    // Update the window with each of the values in turn.
+    pub fn array_update(&mut self, data: &[f64]) -> Result<()> {
+        for v in data {
+            self.update(*v)?;
+        }
+        Ok(())
+    }
+
+    // The stats of the values in the window.
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    // The values in the window, from the oldest.
+    pub fn values(&self) -> impl Iterator<Item = f64> + '_ {
+        (0..self.len).map(|i| self.values[(self.start + i) % N])
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    // Recalculate the stats of the full window, from the oldest value.
+    fn recalculate(&mut self) -> Result<()> {
+        let (newer, older) = self.values.split_at(self.start);
+        let mut stats = Stats::with_moments(self.stats.moments());
+        stats.array_update(older)?;
+        stats.array_update(newer)?;
+        self.stats = stats;
+        self.removed = 0;
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for Window<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}